@@ -2,6 +2,7 @@ use futures::stream::StreamExt;
 use http_client::h1::H1Client;
 use spotify_api::api::albums::album_tracks;
 use spotify_api::client::BasicSpotifyClient;
+use spotify_api::model::id::AlbumId;
 use spotify_api::model::track::SimplifiedTrack;
 use spotify_api::oauth::FileCache;
 use std::path::PathBuf;
@@ -17,9 +18,9 @@ struct Opt {
     #[structopt(short, long, parse(from_os_str))]
     token_path: PathBuf,
 
-    /// Album ID provided by Spotify
+    /// Album ID, URI or open.spotify.com link
     #[structopt(short = "i", long)]
-    album_id: String,
+    album_id: AlbumId,
 }
 
 #[async_std::main]
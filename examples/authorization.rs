@@ -50,12 +50,12 @@ async fn main() -> Result<(), anyhow::Error> {
         redirect_uri,
     } = Opt::from_args();
 
-    let credentials = ClientCredentials::new(client_id, client_secret, redirect_uri.into_string());
+    let credentials = ClientCredentials::new(client_id, client_secret, redirect_uri.into());
     let mut token_cache = FileCache::new(ticket_path)?;
 
     // Force showing a dialog each time because this is a demonstration
     let auth_url = authorize_url(&credentials, None, None, Some(true))?;
-    let auth_url = auth_url.into_string();
+    let auth_url: String = auth_url.into();
 
     println!("Opening browser to url={}\nAfter approving access, please paste the URL you were redirected to.", &auth_url);
     webbrowser::open(&auth_url)?;
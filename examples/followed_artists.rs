@@ -1,6 +1,6 @@
 use futures::stream::StreamExt;
 use http_client::h1::H1Client;
-use spotify_api::api::follow::user_followed_artists;
+use spotify_api::api::follow::user_followed_artists_stream;
 use spotify_api::client::BasicSpotifyClient;
 use spotify_api::oauth::FileCache;
 use std::path::PathBuf;
@@ -8,8 +8,8 @@ use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(
-    name = "spotify-album-tracks",
-    about = "Demonstration of retrieving tracks from a specific album."
+    name = "spotify-followed-artists",
+    about = "Demonstration of retrieving all artists followed by the current user."
 )]
 struct Opt {
     /// Path to the Spotify authorization token
@@ -24,10 +24,8 @@ async fn main() -> Result<(), anyhow::Error> {
     let token_cache = FileCache::new(token_path)?;
     let client = BasicSpotifyClient::new(H1Client::new(), token_cache);
 
-    // While we _can_ retrieve just the first page, we can also get a streaming iterator
-    // that resolves all tracks in the next pages.
-    let followed = user_followed_artists(&client).await?;
-    let mut stream = followed.artists.into_stream(&client)?;
+    // Followed artists are paged by cursor, so stream them rather than fetching a single page
+    let mut stream = user_followed_artists_stream(&client, None);
 
     while let Some(artist) = stream.next().await {
         let artist = artist?;
@@ -1,15 +1,16 @@
+use crate::api::chunks::chunked;
 use crate::api::client::ClientExt;
 use crate::api::SpotifyClient;
 use crate::model::album::{FullAlbum, FullAlbums};
+use crate::model::id::AlbumId;
 use crate::model::page::Page;
 use crate::model::track::SimplifiedTrack;
 use crate::Result;
 use http_types::{Method, Request, Url};
-use std::borrow::Borrow;
 
 pub async fn album_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &AlbumId,
     market: Option<&str>,
 ) -> Result<FullAlbum> {
     let url = format!("https://api.spotify.com/v1/albums/{}", id);
@@ -21,13 +22,13 @@ pub async fn album_with_options<C: SpotifyClient + ?Sized>(
     client.send_authorized(req).deserialize_response().await
 }
 
-pub async fn album<C: SpotifyClient + ?Sized>(client: &C, id: &str) -> Result<FullAlbum> {
+pub async fn album<C: SpotifyClient + ?Sized>(client: &C, id: &AlbumId) -> Result<FullAlbum> {
     album_with_options(client, id, None).await
 }
 
 pub async fn album_tracks_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &AlbumId,
     limit: Option<usize>,
     offset: Option<usize>,
     market: Option<&str>,
@@ -44,28 +45,38 @@ pub async fn album_tracks_with_options<C: SpotifyClient + ?Sized>(
 
 pub async fn album_tracks<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &AlbumId,
 ) -> Result<Page<SimplifiedTrack>> {
     album_tracks_with_options(client, id, None, None, None).await
 }
 
-pub async fn albums_with_options<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+const MAX_ALBUM_IDS: usize = 20;
+
+async fn albums_chunk<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[AlbumId],
     market: Option<&str>,
-) -> Result<FullAlbums> {
-    // TODO: reject if more than 20 IDs? Or let the users handle that?
-    let mut url = Url::parse(&format!("https://api.spotify.com/v1/albums"))?;
+) -> Result<Vec<Option<FullAlbum>>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/albums").unwrap();
     set_query_param!(url, market);
     set_query_param_joined!(url, ids);
 
     let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+    let response: FullAlbums = client.send_authorized(req).deserialize_response().await?;
+    Ok(response.albums)
 }
 
-pub async fn albums<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+/// Get catalog information for any number of albums, requested 20 at a time.
+pub async fn albums_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[AlbumId],
+    market: Option<&str>,
 ) -> Result<FullAlbums> {
+    let albums = chunked(ids, MAX_ALBUM_IDS, |ids| albums_chunk(client, ids, market)).await?;
+    Ok(FullAlbums { albums })
+}
+
+pub async fn albums<C: SpotifyClient + ?Sized>(client: &C, ids: &[AlbumId]) -> Result<FullAlbums> {
     albums_with_options(client, ids, None).await
 }
@@ -1,16 +1,17 @@
+use crate::api::chunks::chunked;
 use crate::api::{ClientExt, SpotifyClient};
 use crate::model::album::SimplifiedAlbum;
-use crate::model::artist::{FullArtist, FullArtists};
+use crate::model::artist::{FullArtist, FullArtists, RelatedArtists};
+use crate::model::id::ArtistId;
 use crate::model::page::Page;
-use crate::model::track::FullTracks;
+use crate::model::track::ArtistTopTracks;
 use crate::Result;
 use http_types::{Method, Request, Url};
-use std::borrow::Borrow;
 
 /// Get Spotify catalog information for a single artist identified by their unique Spotify ID.
 ///
 /// https://developer.spotify.com/documentation/web-api/reference/artists/get-artist/
-pub async fn artist<C: SpotifyClient + ?Sized>(client: &C, id: &str) -> Result<FullArtist> {
+pub async fn artist<C: SpotifyClient + ?Sized>(client: &C, id: &ArtistId) -> Result<FullArtist> {
     let url = format!("https://api.spotify.com/v1/artists/{}", id);
     let url = Url::parse(&url)?;
 
@@ -20,7 +21,7 @@ pub async fn artist<C: SpotifyClient + ?Sized>(client: &C, id: &str) -> Result<F
 
 pub async fn artist_albums_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &ArtistId,
     include_groups: Option<&str>,
     country: Option<&str>,
     limit: Option<usize>,
@@ -40,16 +41,16 @@ pub async fn artist_albums_with_options<C: SpotifyClient + ?Sized>(
 
 pub async fn artist_albums<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &ArtistId,
 ) -> Result<Page<SimplifiedAlbum>> {
     artist_albums_with_options(client, id, None, None, None, None).await
 }
 
 pub async fn top_tracks_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &ArtistId,
     country: Option<&str>,
-) -> Result<FullTracks> {
+) -> Result<ArtistTopTracks> {
     let url = format!("https://api.spotify.com/v1/artists/{}/top-tracks", id);
     let mut url = Url::parse(&url)?;
 
@@ -59,14 +60,17 @@ pub async fn top_tracks_with_options<C: SpotifyClient + ?Sized>(
     client.send_authorized(req).deserialize_response().await
 }
 
-pub async fn top_tracks<C: SpotifyClient + ?Sized>(client: &C, id: &str) -> Result<FullTracks> {
+pub async fn top_tracks<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &ArtistId,
+) -> Result<ArtistTopTracks> {
     top_tracks_with_options(client, id, None).await
 }
 
 pub async fn related_artists<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
-) -> Result<FullArtists> {
+    id: &ArtistId,
+) -> Result<RelatedArtists> {
     let url = format!("https://api.spotify.com/v1/artists/{}/related-artists", id);
     let url = Url::parse(&url)?;
 
@@ -74,14 +78,26 @@ pub async fn related_artists<C: SpotifyClient + ?Sized>(
     client.send_authorized(req).deserialize_response().await
 }
 
-pub async fn artists<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+const MAX_ARTIST_IDS: usize = 50;
+
+async fn artists_chunk<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
-) -> Result<FullArtists> {
+    ids: &[ArtistId],
+) -> Result<Vec<Option<FullArtist>>> {
     // UNWRAP: Known-valid URL
     let mut url = Url::parse("https://api.spotify.com/v1/artists").unwrap();
     set_query_param_joined!(url, ids);
 
     let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+    let response: FullArtists = client.send_authorized(req).deserialize_response().await?;
+    Ok(response.artists)
+}
+
+/// Get catalog information for any number of artists, requested 50 at a time.
+pub async fn artists<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[ArtistId],
+) -> Result<FullArtists> {
+    let artists = chunked(ids, MAX_ARTIST_IDS, |ids| artists_chunk(client, ids)).await?;
+    Ok(FullArtists { artists })
 }
@@ -0,0 +1,92 @@
+use crate::api::chunks::chunked;
+use crate::api::{ClientExt, SpotifyClient};
+use crate::model::audiobook::{FullAudiobook, FullAudiobooks, SimplifiedChapter};
+use crate::model::id::AudiobookId;
+use crate::model::page::Page;
+use crate::Result;
+
+use http_types::{Method, Request, Url};
+
+pub async fn audiobook_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &AudiobookId,
+    market: Option<&str>,
+) -> Result<FullAudiobook> {
+    let url = format!("https://api.spotify.com/v1/audiobooks/{}", id);
+    let mut url = Url::parse(&url)?;
+
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn audiobook<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &AudiobookId,
+) -> Result<FullAudiobook> {
+    audiobook_with_options(client, id, None).await
+}
+
+const MAX_AUDIOBOOK_IDS: usize = 50;
+
+async fn audiobooks_chunk<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[AudiobookId],
+    market: Option<&str>,
+) -> Result<Vec<Option<FullAudiobook>>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/audiobooks").unwrap();
+
+    set_query_param!(url, market);
+    set_query_param_joined!(url, ids);
+
+    let req = Request::new(Method::Get, url);
+    let response: FullAudiobooks = client.send_authorized(req).deserialize_response().await?;
+    Ok(response.audiobooks)
+}
+
+/// Get catalog information for any number of audiobooks, requested 50 at a time.
+pub async fn audiobooks_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[AudiobookId],
+    market: Option<&str>,
+) -> Result<FullAudiobooks> {
+    let audiobooks = chunked(ids, MAX_AUDIOBOOK_IDS, |ids| {
+        audiobooks_chunk(client, ids, market)
+    })
+    .await?;
+    Ok(FullAudiobooks { audiobooks })
+}
+
+pub async fn audiobooks<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[AudiobookId],
+) -> Result<FullAudiobooks> {
+    audiobooks_with_options(client, ids, None).await
+}
+
+pub async fn audiobook_chapters_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &AudiobookId,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    market: Option<&str>,
+) -> Result<Page<SimplifiedChapter>> {
+    let url = format!("https://api.spotify.com/v1/audiobooks/{}/chapters", id);
+    let mut url = Url::parse(&url)?;
+
+    set_query_param!(url, limit);
+    set_query_param!(url, offset);
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn audiobook_chapters<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &AudiobookId,
+) -> Result<Page<SimplifiedChapter>> {
+    audiobook_chapters_with_options(client, id, None, None, None).await
+}
@@ -0,0 +1,310 @@
+//! Coalescing single-item lookups from concurrent callers into multi-ID requests.
+use futures::channel::oneshot;
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::api::{albums, artists, audiobooks, chapters, episodes, shows, tracks};
+use crate::api::{Error, SpotifyClient};
+use crate::model::album::FullAlbum;
+use crate::model::artist::FullArtist;
+use crate::model::audio::AudioFeatures;
+use crate::model::audiobook::{FullAudiobook, FullChapter};
+use crate::model::id::{AlbumId, ArtistId, AudiobookId, ChapterId, EpisodeId, ShowId, TrackId};
+use crate::model::show::{FullEpisode, SimplifiedShow};
+use crate::model::track::FullTrack;
+use crate::Result;
+
+const DEFAULT_WINDOW: Duration = Duration::from_millis(10);
+
+type Waiter<T> = oneshot::Sender<Result<Option<T>>>;
+
+struct Batch<I, T> {
+    generation: u64,
+    deadline: Instant,
+    ids: Vec<I>,
+    // Index into `ids` for each caller; callers asking for the same ID share an entry
+    waiters: Vec<(usize, Waiter<T>)>,
+}
+
+type Taken<I, T> = (Vec<I>, Vec<(usize, Waiter<T>)>);
+
+struct Lane<I, T> {
+    max_ids: usize,
+    batch: Mutex<Batch<I, T>>,
+}
+
+struct Joined<I, T> {
+    generation: u64,
+    deadline: Instant,
+    receiver: oneshot::Receiver<Result<Option<T>>>,
+    // Set when this caller filled up the batch and should send it right away
+    full: Option<Taken<I, T>>,
+}
+
+impl<I: Clone + PartialEq, T> Lane<I, T> {
+    fn new(max_ids: usize) -> Self {
+        Lane {
+            max_ids,
+            batch: Mutex::new(Batch {
+                generation: 0,
+                deadline: Instant::now(),
+                ids: Vec::new(),
+                waiters: Vec::new(),
+            }),
+        }
+    }
+
+    fn join(&self, id: &I, window: Duration) -> Joined<I, T> {
+        let (sender, receiver) = oneshot::channel();
+        // UNWRAP: The lock is never held across a panic
+        let mut batch = self.batch.lock().unwrap();
+        if batch.waiters.is_empty() {
+            batch.deadline = Instant::now() + window;
+        }
+        let index = match batch.ids.iter().position(|i| i == id) {
+            Some(index) => index,
+            None => {
+                batch.ids.push(id.clone());
+                batch.ids.len() - 1
+            }
+        };
+        batch.waiters.push((index, sender));
+
+        let generation = batch.generation;
+        let full = if batch.ids.len() >= self.max_ids {
+            Some(Self::take_locked(&mut batch))
+        } else {
+            None
+        };
+        Joined {
+            generation,
+            deadline: batch.deadline,
+            receiver,
+            full,
+        }
+    }
+
+    /// Takes the batch out for sending, unless another caller already did.
+    fn take(&self, generation: u64) -> Option<Taken<I, T>> {
+        // UNWRAP: The lock is never held across a panic
+        let mut batch = self.batch.lock().unwrap();
+        if batch.generation == generation {
+            Some(Self::take_locked(&mut batch))
+        } else {
+            None
+        }
+    }
+
+    fn take_locked(batch: &mut Batch<I, T>) -> Taken<I, T> {
+        batch.generation += 1;
+        (
+            std::mem::take(&mut batch.ids),
+            std::mem::take(&mut batch.waiters),
+        )
+    }
+}
+
+// Every caller in a batch gets its own error. API errors are copied so that callers can still
+// tell a rate limit from a bad request; anything else is shared.
+fn share_error(e: Error) -> impl Fn() -> Error {
+    let e = Arc::new(e);
+    move || match &*e {
+        Error::Api { status, message } => Error::Api {
+            status: *status,
+            message: message.clone(),
+        },
+        _ => Error::Shared(Arc::clone(&e)),
+    }
+}
+
+async fn send_batch<I, T, F, Fut>(batch: Taken<I, T>, fetch: &F)
+where
+    T: Clone,
+    F: Fn(Vec<I>) -> Fut,
+    Fut: Future<Output = Result<Vec<Option<T>>>>,
+{
+    let (ids, waiters) = batch;
+    match fetch(ids).await {
+        Ok(items) => {
+            for (index, waiter) in waiters {
+                // A caller that went away doesn't need its result
+                let _ = waiter.send(Ok(items.get(index).cloned().flatten()));
+            }
+        }
+        Err(e) => {
+            let error = share_error(e);
+            for (_, waiter) in waiters {
+                let _ = waiter.send(Err(error()));
+            }
+        }
+    }
+}
+
+async fn lookup<I, T, F, Fut>(
+    lane: &Lane<I, T>,
+    window: Duration,
+    id: &I,
+    fetch: F,
+) -> Result<Option<T>>
+where
+    I: Clone + PartialEq,
+    T: Clone,
+    F: Fn(Vec<I>) -> Fut,
+    Fut: Future<Output = Result<Vec<Option<T>>>>,
+{
+    let Joined {
+        generation,
+        deadline,
+        mut receiver,
+        full,
+    } = lane.join(id, window);
+
+    if let Some(batch) = full {
+        send_batch(batch, &fetch).await;
+    } else {
+        // Whoever wakes up first after the window sends the batch for everyone in it
+        let delay = Delay::new(deadline.saturating_duration_since(Instant::now()));
+        match future::select(delay, &mut receiver).await {
+            Either::Left(_) => {
+                if let Some(batch) = lane.take(generation) {
+                    send_batch(batch, &fetch).await;
+                }
+            }
+            Either::Right((Ok(result), _)) => return result,
+            Either::Right((Err(oneshot::Canceled), _)) => {}
+        }
+    }
+
+    match receiver.await {
+        Ok(result) => result,
+        // The caller that was sending our batch went away before it finished
+        Err(oneshot::Canceled) => Ok(fetch(vec![id.clone()]).await?.pop().flatten()),
+    }
+}
+
+/// Collects single-item lookups made at about the same time into multi-ID requests.
+///
+/// The first lookup of a kind opens a batch that stays open for a short window; every lookup of
+/// the same kind made during that window joins it, and a single request is sent for all of them
+/// when it closes, or as soon as it holds as many IDs as Spotify accepts at once. Each caller
+/// gets back only the item it asked for, or `None` if Spotify doesn't know the ID.
+///
+/// A batcher is meant to be shared between tasks, for example in an `Arc`. Wrap the client in a
+/// [`DedupSpotifyClient`](crate::api::dedup::DedupSpotifyClient) to also share responses between
+/// other identical requests.
+pub struct Batcher<C> {
+    client: C,
+    window: Duration,
+    market: Option<String>,
+    albums: Lane<AlbumId, FullAlbum>,
+    artists: Lane<ArtistId, FullArtist>,
+    audio_features: Lane<TrackId, AudioFeatures>,
+    audiobooks: Lane<AudiobookId, FullAudiobook>,
+    chapters: Lane<ChapterId, FullChapter>,
+    episodes: Lane<EpisodeId, FullEpisode>,
+    shows: Lane<ShowId, SimplifiedShow>,
+    tracks: Lane<TrackId, FullTrack>,
+}
+
+impl<C: SpotifyClient> Batcher<C> {
+    /// Batch lookups made within 10 milliseconds of each other.
+    pub fn new(client: C) -> Self {
+        Self::with_options(client, DEFAULT_WINDOW, None)
+    }
+
+    pub fn with_options(client: C, window: Duration, market: Option<String>) -> Self {
+        Batcher {
+            client,
+            window,
+            market,
+            albums: Lane::new(20),
+            artists: Lane::new(50),
+            audio_features: Lane::new(100),
+            audiobooks: Lane::new(50),
+            chapters: Lane::new(50),
+            episodes: Lane::new(50),
+            shows: Lane::new(50),
+            tracks: Lane::new(50),
+        }
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    pub async fn album(&self, id: &AlbumId) -> Result<Option<FullAlbum>> {
+        let market = self.market.as_deref();
+        lookup(&self.albums, self.window, id, |ids| async move {
+            let albums = albums::albums_with_options(&self.client, &ids, market).await?;
+            Ok(albums.albums)
+        })
+        .await
+    }
+
+    pub async fn artist(&self, id: &ArtistId) -> Result<Option<FullArtist>> {
+        lookup(&self.artists, self.window, id, |ids| async move {
+            Ok(artists::artists(&self.client, &ids).await?.artists)
+        })
+        .await
+    }
+
+    pub async fn audio_features(&self, id: &TrackId) -> Result<Option<AudioFeatures>> {
+        lookup(&self.audio_features, self.window, id, |ids| async move {
+            let features = tracks::several_audio_features(&self.client, &ids).await?;
+            Ok(features.audio_features)
+        })
+        .await
+    }
+
+    pub async fn audiobook(&self, id: &AudiobookId) -> Result<Option<FullAudiobook>> {
+        let market = self.market.as_deref();
+        lookup(&self.audiobooks, self.window, id, |ids| async move {
+            let audiobooks =
+                audiobooks::audiobooks_with_options(&self.client, &ids, market).await?;
+            Ok(audiobooks.audiobooks)
+        })
+        .await
+    }
+
+    pub async fn chapter(&self, id: &ChapterId) -> Result<Option<FullChapter>> {
+        let market = self.market.as_deref();
+        lookup(&self.chapters, self.window, id, |ids| async move {
+            let chapters = chapters::chapters_with_options(&self.client, &ids, market).await?;
+            Ok(chapters.chapters)
+        })
+        .await
+    }
+
+    pub async fn episode(&self, id: &EpisodeId) -> Result<Option<FullEpisode>> {
+        let market = self.market.as_deref();
+        lookup(&self.episodes, self.window, id, |ids| async move {
+            let episodes = episodes::episodes_with_options(&self.client, &ids, market).await?;
+            Ok(episodes.episodes)
+        })
+        .await
+    }
+
+    /// Spotify only returns simplified shows when asked for several at once.
+    pub async fn show(&self, id: &ShowId) -> Result<Option<SimplifiedShow>> {
+        let market = self.market.as_deref();
+        lookup(&self.shows, self.window, id, |ids| async move {
+            Ok(shows::shows_with_options(&self.client, &ids, market)
+                .await?
+                .shows)
+        })
+        .await
+    }
+
+    pub async fn track(&self, id: &TrackId) -> Result<Option<FullTrack>> {
+        let market = self.market.as_deref();
+        lookup(&self.tracks, self.window, id, |ids| async move {
+            Ok(tracks::tracks_with_options(&self.client, &ids, market)
+                .await?
+                .tracks)
+        })
+        .await
+    }
+}
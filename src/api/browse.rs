@@ -1,11 +1,12 @@
 use crate::api::client::{ClientExt, SpotifyClient};
 use crate::model::album::NewReleases;
 use crate::model::category::Category;
+use crate::model::id::{ArtistId, TrackId};
 use crate::model::page::Page;
 use crate::model::playlist::{FeaturedPlaylists, SimplifiedPlaylist};
-use crate::model::recommend::Recommendations;
+use crate::model::recommend::{GenreSeeds, Recommendations};
 use crate::model::senum::TrackAttribute;
-use crate::Result;
+use crate::{Error, Result};
 use http_types::{Method, Request, Url};
 use std::borrow::Borrow;
 
@@ -135,55 +136,138 @@ pub async fn new_releases<C: SpotifyClient + ?Sized>(client: &C) -> Result<NewRe
     new_releases_with_options(client, None, None, None).await
 }
 
-pub async fn recommendations_with_options<
-    C: SpotifyClient + ?Sized,
-    B1: Borrow<str>,
-    B2: Borrow<str>,
-    B3: Borrow<str>,
->(
+/// Seeds and tunable track attributes of a request for recommendations. Spotify needs between
+/// one and five seeds in total across artists, genres and tracks.
+///
+/// ```rust
+/// use spotify_api::api::browse::RecommendationOptions;
+/// use spotify_api::model::senum::TrackAttribute;
+///
+/// let options = RecommendationOptions {
+///     seed_genres: &["acoustic", "afrobeat"],
+///     target_attributes: &[TrackAttribute::Energy(0.8)],
+///     ..RecommendationOptions::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RecommendationOptions<'a> {
+    pub limit: Option<usize>,
+    pub market: Option<&'a str>,
+    pub max_attributes: &'a [TrackAttribute],
+    pub min_attributes: &'a [TrackAttribute],
+    pub target_attributes: &'a [TrackAttribute],
+    pub seed_artists: &'a [ArtistId],
+    pub seed_genres: &'a [&'a str],
+    pub seed_tracks: &'a [TrackId],
+}
+
+fn append_attribute(url: &mut Url, prefix: &str, attribute: &TrackAttribute) {
+    let (name, value) = attribute.name_and_value();
+    url.query_pairs_mut()
+        .append_pair(&format!("{}{}", prefix, name), &value);
+}
+
+/// Get recommendations for the seeds and attributes in `options`, without checking the seeds.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/browse/get-recommendations/)
+pub async fn recommendations_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    limit: Option<usize>,
-    market: Option<&str>,
-    max_attributes: &[TrackAttribute],
-    min_attributes: &[TrackAttribute],
-    target_attributes: &[TrackAttribute],
-    seed_artists: Option<&[B1]>,
-    seed_genres: Option<&[B2]>,
-    seed_tracks: Option<&[B3]>,
+    options: &RecommendationOptions<'_>,
 ) -> Result<Recommendations> {
     // UNWRAP: Known-valid URL
     let mut url = Url::parse("https://api.spotify.com/v1/recommendations").unwrap();
 
+    let RecommendationOptions {
+        limit,
+        market,
+        seed_artists,
+        seed_genres,
+        seed_tracks,
+        ..
+    } = *options;
+
     set_query_param!(url, limit);
     set_query_param!(url, market);
 
-    for a in max_attributes {
-        let param = a.fmt_prefixed("max_");
-        url.set_query(Some(param.as_str()));
+    for a in options.max_attributes {
+        append_attribute(&mut url, "max_", a);
     }
 
-    for a in min_attributes {
-        let param = a.fmt_prefixed("min_");
-        url.set_query(Some(param.as_str()));
+    for a in options.min_attributes {
+        append_attribute(&mut url, "min_", a);
     }
 
-    for a in target_attributes {
-        let param = a.fmt_prefixed("target_");
-        url.set_query(Some(param.as_str()));
+    for a in options.target_attributes {
+        append_attribute(&mut url, "target_", a);
     }
 
-    if let Some(seed_artists) = seed_artists {
-        set_query_param_joined!(url, seed_artists);
-    }
+    set_query_param_joined!(url, seed_artists);
+    set_query_param_joined!(url, seed_genres);
+    set_query_param_joined!(url, seed_tracks);
 
-    if let Some(seed_genres) = seed_genres {
-        set_query_param_joined!(url, seed_genres);
-    }
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
 
-    if let Some(seed_tracks) = seed_tracks {
-        set_query_param_joined!(url, seed_tracks);
-    }
+/// Get the genres that may be used as `seed_genres` for recommendations.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/browse/get-recommendation-genres/)
+pub async fn available_genre_seeds<C: SpotifyClient + ?Sized>(client: &C) -> Result<GenreSeeds> {
+    // UNWRAP: Known-valid URL
+    let url =
+        Url::parse("https://api.spotify.com/v1/recommendations/available-genre-seeds").unwrap();
 
     let req = Request::new(Method::Get, url);
     client.send_authorized(req).deserialize_response().await
 }
+
+const MAX_SEEDS: usize = 5;
+
+/// Check the seeds of `options` before sending them: there must be between one and five seeds
+/// across artists, genres and tracks, and every genre must be in `available_genres`.
+pub fn check_seeds<G: Borrow<str>>(
+    available_genres: &[G],
+    options: &RecommendationOptions<'_>,
+) -> Result<()> {
+    let count = options.seed_artists.len() + options.seed_genres.len() + options.seed_tracks.len();
+    if count == 0 || count > MAX_SEEDS {
+        return Err(Error::SeedCount(count));
+    }
+
+    for genre in options.seed_genres {
+        if !available_genres.iter().any(|g| g.borrow() == *genre) {
+            return Err(Error::UnknownGenreSeed((*genre).to_owned()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Same as `recommendations_with_options`, but checks the seeds with `check_seeds` against
+/// `available_genres` before requesting recommendations. Fetch the genres once with
+/// `available_genre_seeds` and reuse them across requests.
+pub async fn checked_recommendations_with_genres<C: SpotifyClient + ?Sized, G: Borrow<str>>(
+    client: &C,
+    available_genres: &[G],
+    options: &RecommendationOptions<'_>,
+) -> Result<Recommendations> {
+    check_seeds(available_genres, options)?;
+    recommendations_with_options(client, options).await
+}
+
+/// Same as `checked_recommendations_with_genres`, but fetches the available genre seeds first.
+/// This costs an extra request whenever there are genre seeds, so prefer
+/// `checked_recommendations_with_genres` when asking for recommendations more than once.
+pub async fn checked_recommendations_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    options: &RecommendationOptions<'_>,
+) -> Result<Recommendations> {
+    // Only fetch the genre list if there's a genre to check it against
+    let available = if options.seed_genres.is_empty() {
+        Vec::new()
+    } else {
+        available_genre_seeds(client).await?.genres
+    };
+
+    checked_recommendations_with_genres(client, &available, options).await
+}
@@ -0,0 +1,392 @@
+//! Caching of catalog responses, revalidated with `ETag` and `If-None-Match`.
+use futures::future::ready;
+use http_client::HttpClient;
+use http_types::headers::{AGE, CACHE_CONTROL, ETAG, IF_NONE_MATCH, VARY};
+use http_types::{Method, Response, StatusCode};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use crate::api::client::HttpClientFuture;
+use crate::api::SpotifyClient;
+use crate::clock::{Clock, SystemClock};
+
+/// A response stored by a [`ResponseCache`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedResponse {
+    scope: String,
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    etag: Option<String>,
+    /// The request headers named by `Vary`, and the values the response was made for
+    vary: Vec<(String, Option<String>)>,
+    stored_at: SystemTime,
+    /// Seconds the response may be used without revalidating
+    max_age: Option<u64>,
+}
+
+impl CachedResponse {
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn is_fresh(&self, now: SystemTime) -> bool {
+        match self.max_age {
+            Some(max_age) => now < self.stored_at + Duration::from_secs(max_age),
+            None => false,
+        }
+    }
+
+    // Whether the response was made for a request with the same headers as `req`, as far as
+    // `Vary` goes
+    fn matches(&self, req: &http_client::Request) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| header_value(req, name) == *value)
+    }
+
+    // Entries can be read back from disk, so the status may not be one at all
+    fn into_response(self) -> Result<Response, http_client::Error> {
+        let mut resp = Response::new(StatusCode::try_from(self.status)?);
+        for (name, value) in &self.headers {
+            resp.append_header(name.as_str(), value.as_str());
+        }
+        resp.set_body(self.body);
+        Ok(resp)
+    }
+
+    // Takes the caching headers of a new response for the same URL, returning whether the entry
+    // may still be kept
+    fn refresh(&mut self, resp: &Response, now: SystemTime) -> bool {
+        let policy = Policy::of(resp);
+        self.max_age = policy.max_age;
+        self.stored_at = now;
+        if let Some(etag) = resp.header(ETAG) {
+            self.etag = Some(etag.as_str().to_owned());
+        }
+        for name in &[ETAG, CACHE_CONTROL] {
+            if let Some(values) = resp.header(name) {
+                self.headers
+                    .retain(|(n, _)| !name.as_str().eq_ignore_ascii_case(n));
+                for value in values {
+                    self.headers
+                        .push((name.as_str().to_owned(), value.as_str().to_owned()));
+                }
+            }
+        }
+        policy.store
+    }
+}
+
+fn header_value(req: &http_client::Request, name: &str) -> Option<String> {
+    req.header(name).map(|v| v.as_str().to_owned())
+}
+
+/// What a response allows a cache to do with it.
+struct Policy {
+    store: bool,
+    max_age: Option<u64>,
+    /// Request headers, other than those every request in a scope shares, that the response
+    /// depends on
+    vary: Vec<String>,
+}
+
+impl Policy {
+    fn of(resp: &Response) -> Self {
+        let mut store = true;
+        let mut max_age = None;
+        let mut revalidate = false;
+        let directives = resp.header(CACHE_CONTROL).into_iter().flatten();
+        for directive in directives.flat_map(|v| v.as_str().split(',')) {
+            let directive = directive.trim().to_ascii_lowercase();
+            match directive.as_str() {
+                // Entries are kept per scope, so `private` responses can be kept too
+                "no-store" => store = false,
+                "no-cache" => revalidate = true,
+                _ => {
+                    if let Some(secs) = directive.strip_prefix("max-age=") {
+                        max_age = secs.trim_matches('"').parse::<u64>().ok();
+                    }
+                }
+            }
+        }
+
+        // Every request in a scope is authorized for the same user, and bodies are decoded by
+        // the time they get here, so only other headers need to match for an entry to be used
+        let mut vary = Vec::new();
+        let varies = resp.header(VARY).into_iter().flatten();
+        for name in varies.flat_map(|v| v.as_str().split(',')) {
+            let name = name.trim().to_ascii_lowercase();
+            match name.as_str() {
+                "*" => store = false,
+                "authorization" | "accept-encoding" | "" => {}
+                _ => vary.push(name),
+            }
+        }
+
+        // Time the response already spent in caches on the way here counts against its max-age
+        let age = resp
+            .header(AGE)
+            .and_then(|v| v.as_str().parse::<u64>().ok())
+            .unwrap_or(0);
+        let max_age = max_age
+            .map(|m| m.saturating_sub(age))
+            .filter(|_| !revalidate);
+
+        Policy {
+            store,
+            max_age,
+            vary,
+        }
+    }
+}
+
+/// Storage for cached responses, keyed by scope and URL.
+///
+/// Caches are best-effort: an entry that can't be read is a miss, and one that can't be written
+/// is dropped.
+pub trait ResponseCache: Debug + Send + Sync + 'static {
+    fn get(&self, scope: &str, url: &str) -> Option<CachedResponse>;
+    fn put(&self, response: CachedResponse);
+}
+
+/// Keeps responses in memory for as long as the cache lives.
+#[derive(Debug, Default)]
+pub struct MemoryResponseCache {
+    responses: Mutex<HashMap<(String, String), CachedResponse>>,
+}
+
+impl MemoryResponseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ResponseCache for MemoryResponseCache {
+    fn get(&self, scope: &str, url: &str) -> Option<CachedResponse> {
+        let key = (scope.to_owned(), url.to_owned());
+        // UNWRAP: The lock is never held across a panic
+        self.responses.lock().unwrap().get(&key).cloned()
+    }
+
+    fn put(&self, response: CachedResponse) {
+        let key = (response.scope.clone(), response.url.clone());
+        // UNWRAP: The lock is never held across a panic
+        self.responses.lock().unwrap().insert(key, response);
+    }
+}
+
+/// Keeps responses as JSON files in a directory, so they outlive the process.
+#[derive(Debug)]
+pub struct FileResponseCache {
+    dir: PathBuf,
+}
+
+impl FileResponseCache {
+    pub fn new(dir: PathBuf) -> std::io::Result<Self> {
+        // Note: to avoid expressing a preference for a specific runtime, this cache uses
+        // synchronous I/O.
+        fs::create_dir_all(&dir)?;
+        Ok(FileResponseCache { dir })
+    }
+
+    fn path(&self, scope: &str, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        (scope, url).hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+}
+
+impl ResponseCache for FileResponseCache {
+    fn get(&self, scope: &str, url: &str) -> Option<CachedResponse> {
+        let bytes = fs::read(self.path(scope, url)).ok()?;
+        let response: CachedResponse = serde_json::from_slice(&bytes).ok()?;
+        // Different keys can hash to the same file, and files can be damaged
+        Some(response)
+            .filter(|r| r.scope == scope && r.url == url && StatusCode::try_from(r.status).is_ok())
+    }
+
+    fn put(&self, response: CachedResponse) {
+        let path = self.path(&response.scope, &response.url);
+        let tmp = path.with_extension("tmp");
+        if let Ok(bytes) = serde_json::to_vec(&response) {
+            if fs::write(&tmp, bytes).is_ok() {
+                let _ = fs::rename(&tmp, &path);
+            }
+        }
+    }
+}
+
+/// Client wrapper that caches successful GET responses.
+///
+/// Responses are served from the cache without a request while their `Cache-Control: max-age`
+/// lasts. After that, or when the response didn't allow it, the request is sent again with
+/// `If-None-Match`, and a `304 Not Modified` answer is served from the cache. Responses marked
+/// `no-store` or `Vary: *`, or without an `ETag` or `max-age`, are not kept.
+///
+/// Entries are kept per `scope`, which names whose token the client sends, such as the ID of the
+/// user it belongs to. Responses for one user, like those marked `private` or that vary by
+/// `Authorization`, can then be cached without another user being served them: clients with
+/// different scopes can share a cache, but never its entries.
+///
+/// Catalog lookups are cached: albums, artists, shows, browse categories, and playlists with
+/// their items. Everything else, like the player or the library, changes too often to be worth
+/// it, so it's passed through.
+#[derive(Debug)]
+pub struct CachingSpotifyClient<C, S, K = SystemClock> {
+    client: C,
+    cache: Arc<S>,
+    scope: String,
+    clock: K,
+}
+
+impl<C: SpotifyClient, S: ResponseCache> CachingSpotifyClient<C, S> {
+    pub fn new(client: C, cache: S, scope: impl Into<String>) -> Self {
+        Self::with_clock(client, cache, scope, SystemClock::new())
+    }
+}
+
+impl<C: SpotifyClient, S: ResponseCache, K> CachingSpotifyClient<C, S, K> {
+    pub fn with_clock(client: C, cache: S, scope: impl Into<String>, clock: K) -> Self {
+        CachingSpotifyClient {
+            client,
+            cache: Arc::new(cache),
+            scope: scope.into(),
+            clock,
+        }
+    }
+
+    pub fn cache(&self) -> &S {
+        &self.cache
+    }
+
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+fn is_cacheable(req: &http_client::Request) -> bool {
+    if req.method() != Method::Get {
+        return false;
+    }
+    let segments: Vec<&str> = req.url().path().split('/').skip(1).collect();
+    matches!(
+        segments.as_slice(),
+        ["v1", "albums", ..]
+            | ["v1", "artists", ..]
+            | ["v1", "shows", ..]
+            | ["v1", "browse", "categories", ..]
+            | ["v1", "playlists", _]
+            | ["v1", "playlists", _, "tracks"]
+    )
+}
+
+impl<C, S, K> HttpClient for CachingSpotifyClient<C, S, K>
+where
+    C: SpotifyClient,
+    S: ResponseCache,
+    K: 'static + Clock + Debug + Send + Sync + Unpin,
+{
+    fn send(&self, req: http_client::Request) -> HttpClientFuture {
+        self.client.send(req)
+    }
+}
+
+impl<C, S, K> SpotifyClient for CachingSpotifyClient<C, S, K>
+where
+    C: SpotifyClient,
+    S: ResponseCache,
+    K: 'static + Clock + Debug + Send + Sync + Unpin,
+{
+    fn send_authorized(&self, mut req: http_client::Request) -> HttpClientFuture {
+        if !is_cacheable(&req) {
+            return self.client.send_authorized(req);
+        }
+
+        let url = req.url().to_string();
+        let now = self.clock.now();
+        let cached = self
+            .cache
+            .get(&self.scope, &url)
+            .filter(|cached| cached.matches(&req));
+        if let Some(cached) = &cached {
+            if cached.is_fresh(now) {
+                return Box::pin(ready(cached.clone().into_response()));
+            }
+            if let Some(etag) = &cached.etag {
+                req.insert_header(IF_NONE_MATCH, etag.as_str());
+            }
+        }
+
+        // Kept to find the values of the headers a response varies by
+        let sent = req.clone();
+        let scope = self.scope.clone();
+        let resp = self.client.send_authorized(req);
+        let cache = Arc::clone(&self.cache);
+        Box::pin(async move {
+            let mut resp = resp.await?;
+            match (resp.status(), cached) {
+                (StatusCode::NotModified, Some(mut cached)) => {
+                    if cached.refresh(&resp, now) {
+                        cache.put(cached.clone());
+                    }
+                    cached.into_response()
+                }
+                (StatusCode::Ok, _) => {
+                    let policy = Policy::of(&resp);
+                    let etag = resp.header(ETAG).map(|v| v.as_str().to_owned());
+                    if !policy.store || (etag.is_none() && policy.max_age.is_none()) {
+                        return Ok(resp);
+                    }
+
+                    let headers = resp
+                        .iter()
+                        .flat_map(|(name, values)| {
+                            values
+                                .iter()
+                                .map(move |v| (name.as_str().to_owned(), v.as_str().to_owned()))
+                        })
+                        .collect();
+                    let vary = policy
+                        .vary
+                        .into_iter()
+                        .map(|name| {
+                            let value = header_value(&sent, &name);
+                            (name, value)
+                        })
+                        .collect();
+                    let cached = CachedResponse {
+                        scope,
+                        url,
+                        status: resp.status().into(),
+                        headers,
+                        body: resp.body_string().await?,
+                        etag,
+                        vary,
+                        stored_at: now,
+                        max_age: policy.max_age,
+                    };
+                    cache.put(cached.clone());
+                    cached.into_response()
+                }
+                _ => Ok(resp),
+            }
+        })
+    }
+}
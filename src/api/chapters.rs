@@ -0,0 +1,62 @@
+use crate::api::chunks::chunked;
+use crate::api::client::{ClientExt, SpotifyClient};
+use crate::model::audiobook::{FullChapter, FullChapters};
+use crate::model::id::ChapterId;
+use crate::Result;
+use http_types::{Method, Request, Url};
+
+pub async fn chapter_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &ChapterId,
+    market: Option<&str>,
+) -> Result<FullChapter> {
+    let url = format!("https://api.spotify.com/v1/chapters/{}", id);
+    let mut url = Url::parse(&url)?;
+
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn chapter<C: SpotifyClient + ?Sized>(client: &C, id: &ChapterId) -> Result<FullChapter> {
+    chapter_with_options(client, id, None).await
+}
+
+const MAX_CHAPTER_IDS: usize = 50;
+
+async fn chapters_chunk<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[ChapterId],
+    market: Option<&str>,
+) -> Result<Vec<Option<FullChapter>>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/chapters").unwrap();
+
+    set_query_param_joined!(url, ids);
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    let response: FullChapters = client.send_authorized(req).deserialize_response().await?;
+    Ok(response.chapters)
+}
+
+/// Get catalog information for any number of chapters, requested 50 at a time.
+pub async fn chapters_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[ChapterId],
+    market: Option<&str>,
+) -> Result<FullChapters> {
+    let chapters = chunked(ids, MAX_CHAPTER_IDS, |ids| {
+        chapters_chunk(client, ids, market)
+    })
+    .await?;
+    Ok(FullChapters { chapters })
+}
+
+pub async fn chapters<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[ChapterId],
+) -> Result<FullChapters> {
+    chapters_with_options(client, ids, None).await
+}
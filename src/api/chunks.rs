@@ -0,0 +1,44 @@
+//! Splitting requests that take more IDs than Spotify accepts at once.
+use crate::Result;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::future::Future;
+
+/// How many requests for one call that only reads may be in flight at the same time.
+pub(crate) const MAX_CONCURRENT_CHUNKS: usize = 4;
+
+/// Calls `request` with consecutive chunks of at most `size` IDs and concatenates the results in
+/// the order of `ids`. No request is sent for an empty slice.
+pub(crate) async fn chunked<'a, I, T, F, Fut>(
+    ids: &'a [I],
+    size: usize,
+    request: F,
+) -> Result<Vec<T>>
+where
+    F: FnMut(&'a [I]) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    let results: Vec<Vec<T>> = stream::iter(ids.chunks(size))
+        .map(request)
+        .buffered(MAX_CONCURRENT_CHUNKS)
+        .try_collect()
+        .await?;
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Like [`chunked`], for requests that change something and don't return anything. Chunks are
+/// sent one at a time in the order of `ids`, and nothing more is sent once a request fails, so
+/// the changes that were made are always for a prefix of `ids`.
+pub(crate) async fn for_each_chunk<'a, I, F, Fut>(
+    ids: &'a [I],
+    size: usize,
+    mut request: F,
+) -> Result<()>
+where
+    F: FnMut(&'a [I]) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    for chunk in ids.chunks(size) {
+        request(chunk).await?;
+    }
+    Ok(())
+}
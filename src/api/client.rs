@@ -20,6 +20,39 @@ pub trait SpotifyClient: HttpClient {
 
 pub trait ClientExt {
     fn deserialize_response<T: DeserializeOwned>(self) -> BoxFuture<'static, Result<T, Error>>;
+    fn expect_success(self) -> BoxFuture<'static, Result<(), Error>>;
+}
+
+// Regular API errors carry an object, while authorization errors follow the OAuth format.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ErrorResponse {
+    Regular { error: RegularError },
+    Authentication { error_description: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct RegularError {
+    message: String,
+}
+
+/// Convert an unsuccessful response into an `Error::Api`, keeping the raw body as the message
+/// if it isn't in a format Spotify documents.
+pub(crate) async fn check_status(
+    mut resp: http_client::Response,
+) -> Result<http_client::Response, Error> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+
+    let body = resp.body_string().await?;
+    let message = match serde_json::from_str::<ErrorResponse>(&body) {
+        Ok(ErrorResponse::Regular { error }) => error.message,
+        Ok(ErrorResponse::Authentication { error_description }) => error_description,
+        Err(_) => body,
+    };
+    Err(Error::Api { status, message })
 }
 
 impl ClientExt for BoxFuture<'static, HttpClientResult> {
@@ -31,10 +64,16 @@ impl ClientExt for BoxFuture<'static, HttpClientResult> {
         // Ultimately: this has exactly the same allocations as writing a manual
         // `impl Future`, so might as well use the easier syntax.
         Box::pin(async move {
-            let mut resp: http_client::Response = self.await?;
+            let mut resp = check_status(self.await?).await?;
             let body = resp.body_string().await?;
-            println!("{}", body);
-            serde_json::from_slice::<T>(&body.as_bytes()).map_err(|e| e.into())
+            serde_json::from_slice::<T>(body.as_bytes()).map_err(|e| e.into())
+        })
+    }
+
+    fn expect_success(self) -> BoxFuture<'static, Result<(), Error>> {
+        Box::pin(async move {
+            check_status(self.await?).await?;
+            Ok(())
         })
     }
 }
@@ -82,8 +121,9 @@ where
         let auth = format!("Bearer {}", access_token);
         req.insert_header(AUTHORIZATION, auth);
 
-        // TODO: Content-Type header?
-        // Likely should only be set if the body is non-empty
+        // There's no need to set `Content-Type` here: `set_body` copies the body's type onto the
+        // request, so JSON bodies are sent as `application/json` and requests without a body
+        // don't need one.
 
         self.send(req)
     }
@@ -0,0 +1,179 @@
+//! Sharing one response between identical GET requests that are in flight at the same time.
+use futures::future::{BoxFuture, FutureExt, Shared, WeakShared};
+use http_client::HttpClient;
+use http_types::headers::{HeaderName, HeaderValues};
+use http_types::{Method, Response, StatusCode};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::api::client::HttpClientFuture;
+use crate::api::SpotifyClient;
+
+// Responses and errors can't be cloned, so the shared request reads everything up front
+#[derive(Clone)]
+struct BufferedResponse {
+    status: StatusCode,
+    headers: Vec<(HeaderName, HeaderValues)>,
+    body: Vec<u8>,
+}
+
+#[derive(Clone)]
+struct BufferedError {
+    status: StatusCode,
+    message: String,
+}
+
+type SharedRequest = BoxFuture<'static, Result<BufferedResponse, BufferedError>>;
+
+// Only callers keep a request alive, so it's dropped along with the last of them even if it never
+// completed
+type InFlight = HashMap<String, WeakShared<SharedRequest>>;
+
+/// One caller's handle on a shared request. The last caller to go away removes the request from
+/// the in-flight map, so requests that are dropped before they complete don't stay there.
+struct Waiter {
+    request: Option<Shared<SharedRequest>>,
+    in_flight: Weak<Mutex<InFlight>>,
+    key: String,
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        // Let go of the request first, so that it can't be upgraded below if this was the last
+        // handle on it
+        self.request.take();
+        if let Some(in_flight) = self.in_flight.upgrade() {
+            // UNWRAP: The lock is never held across a panic
+            let mut in_flight = in_flight.lock().unwrap();
+            let abandoned =
+                matches!(in_flight.get(&self.key), Some(request) if request.upgrade().is_none());
+            if abandoned {
+                in_flight.remove(&self.key);
+            }
+        }
+    }
+}
+
+impl BufferedResponse {
+    async fn read(mut resp: Response) -> Result<Self, http_client::Error> {
+        let body = resp.body_bytes().await?;
+        let headers = resp
+            .iter()
+            .map(|(name, values)| (name.clone(), values.clone()))
+            .collect();
+        Ok(BufferedResponse {
+            status: resp.status(),
+            headers,
+            body,
+        })
+    }
+
+    fn into_response(self) -> Response {
+        let mut resp = Response::new(self.status);
+        for (name, values) in self.headers {
+            for value in values {
+                resp.append_header(name.clone(), value);
+            }
+        }
+        resp.set_body(self.body);
+        resp
+    }
+}
+
+impl From<http_client::Error> for BufferedError {
+    fn from(e: http_client::Error) -> Self {
+        BufferedError {
+            status: e.status(),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// Client wrapper that sends identical authorized GET requests only once while the first one is
+/// still in flight, and hands every caller its own copy of the response.
+///
+/// Other methods, and requests that aren't authorized, are passed through unchanged.
+pub struct DedupSpotifyClient<C> {
+    client: C,
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+impl<C: SpotifyClient> DedupSpotifyClient<C> {
+    pub fn new(client: C) -> Self {
+        DedupSpotifyClient {
+            client,
+            in_flight: Arc::default(),
+        }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for DedupSpotifyClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // UNWRAP: The lock is never held across a panic
+        let in_flight = self.in_flight.lock().unwrap().len();
+        f.debug_struct("DedupSpotifyClient")
+            .field("client", &self.client)
+            .field("in_flight", &in_flight)
+            .finish()
+    }
+}
+
+impl<C: SpotifyClient> HttpClient for DedupSpotifyClient<C> {
+    fn send(&self, req: http_client::Request) -> HttpClientFuture {
+        self.client.send(req)
+    }
+}
+
+impl<C: SpotifyClient> SpotifyClient for DedupSpotifyClient<C> {
+    fn send_authorized(&self, req: http_client::Request) -> HttpClientFuture {
+        if req.method() != Method::Get {
+            return self.client.send_authorized(req);
+        }
+
+        let key = req.url().to_string();
+        let shared = {
+            // UNWRAP: The lock is never held across a panic
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key).and_then(WeakShared::upgrade) {
+                Some(shared) => shared,
+                None => {
+                    let resp = self.client.send_authorized(req);
+                    let map = Arc::clone(&self.in_flight);
+                    let done_key = key.clone();
+                    let shared = async move {
+                        let result = match resp.await {
+                            Ok(resp) => BufferedResponse::read(resp).await,
+                            Err(e) => Err(e),
+                        };
+                        // UNWRAP: The lock is never held across a panic
+                        map.lock().unwrap().remove(&done_key);
+                        result.map_err(BufferedError::from)
+                    }
+                    .boxed()
+                    .shared();
+                    // UNWRAP: The request hasn't been polled, so it can't have completed
+                    in_flight.insert(key.clone(), shared.downgrade().unwrap());
+                    shared
+                }
+            }
+        };
+
+        let mut waiter = Waiter {
+            request: Some(shared),
+            in_flight: Arc::downgrade(&self.in_flight),
+            key,
+        };
+        Box::pin(async move {
+            // UNWRAP: The request is only taken when the waiter is dropped
+            match waiter.request.as_mut().unwrap().await {
+                Ok(resp) => Ok(resp.into_response()),
+                Err(e) => Err(http_client::Error::from_str(e.status, e.message)),
+            }
+        })
+    }
+}
@@ -1,12 +1,13 @@
+use crate::api::chunks::chunked;
 use crate::api::client::{ClientExt, SpotifyClient};
+use crate::model::id::EpisodeId;
 use crate::model::show::{FullEpisode, FullEpisodes};
 use crate::Result;
 use http_types::{Method, Request, Url};
-use std::borrow::Borrow;
 
 pub async fn episode_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &EpisodeId,
     market: Option<&str>,
 ) -> Result<FullEpisode> {
     let url = format!("https://api.spotify.com/v1/episodes/{}", id);
@@ -18,15 +19,17 @@ pub async fn episode_with_options<C: SpotifyClient + ?Sized>(
     client.send_authorized(req).deserialize_response().await
 }
 
-pub async fn episode<C: SpotifyClient + ?Sized>(client: &C, id: &str) -> Result<FullEpisode> {
+pub async fn episode<C: SpotifyClient + ?Sized>(client: &C, id: &EpisodeId) -> Result<FullEpisode> {
     episode_with_options(client, id, None).await
 }
 
-pub async fn episodes_with_options<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+const MAX_EPISODE_IDS: usize = 50;
+
+async fn episodes_chunk<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[EpisodeId],
     market: Option<&str>,
-) -> Result<FullEpisodes> {
+) -> Result<Vec<Option<FullEpisode>>> {
     // UNWRAP: Known-valid URL
     let mut url = Url::parse("https://api.spotify.com/v1/episodes").unwrap();
 
@@ -34,12 +37,26 @@ pub async fn episodes_with_options<C: SpotifyClient + ?Sized, B: Borrow<str>>(
     set_query_param!(url, market);
 
     let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+    let response: FullEpisodes = client.send_authorized(req).deserialize_response().await?;
+    Ok(response.episodes)
+}
+
+/// Get catalog information for any number of episodes, requested 50 at a time.
+pub async fn episodes_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[EpisodeId],
+    market: Option<&str>,
+) -> Result<FullEpisodes> {
+    let episodes = chunked(ids, MAX_EPISODE_IDS, |ids| {
+        episodes_chunk(client, ids, market)
+    })
+    .await?;
+    Ok(FullEpisodes { episodes })
 }
 
-pub async fn episodes<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn episodes<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[EpisodeId],
 ) -> Result<FullEpisodes> {
     episodes_with_options(client, ids, None).await
 }
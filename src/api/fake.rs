@@ -0,0 +1,1140 @@
+//! An in-memory stand-in for the Spotify Web API that keeps the changes made through it.
+//!
+//! ```rust,ignore
+//! let spotify = FakeSpotify::new(me);
+//! spotify.add_track(track.clone());
+//!
+//! library::library_save_tracks(&spotify, &[track_id.clone()]).await?;
+//! let saved = library::library_get_tracks(&spotify).await?;
+//! assert_eq!(saved.items[0].track.id, Some(track_id));
+//! ```
+use http_client::HttpClient;
+use http_types::headers::RETRY_AFTER;
+use http_types::url::Position;
+use http_types::{Method, Response, StatusCode, Url};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::api::client::HttpClientFuture;
+use crate::api::SpotifyClient;
+use crate::model::album::FullAlbum;
+use crate::model::artist::FullArtist;
+use crate::model::audiobook::SimplifiedAudiobook;
+use crate::model::playlist::FullPlaylist;
+use crate::model::show::{FullEpisode, SimplifiedShow};
+use crate::model::timestamp::Timestamp;
+use crate::model::track::FullTrack;
+use crate::model::user::PublicUser;
+
+type Failure = (StatusCode, String);
+type Reply = Result<(StatusCode, Value), Failure>;
+
+fn reject<T>(status: StatusCode, message: impl Into<String>) -> Result<T, Failure> {
+    Err((status, message.into()))
+}
+
+fn not_found<T>() -> Result<T, Failure> {
+    reject(StatusCode::NotFound, "Resource not found")
+}
+
+fn ok(value: Value) -> Reply {
+    Ok((StatusCode::Ok, value))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    // UNWRAP: Models only contain types that serialize to JSON
+    serde_json::to_value(value).unwrap()
+}
+
+fn timestamp(time: SystemTime) -> Value {
+    to_value(&Timestamp::from(time))
+}
+
+fn unix_millis(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+fn param(url: &Url, name: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v.into_owned())
+}
+
+fn number_param(url: &Url, name: &str) -> Result<Option<usize>, Failure> {
+    match param(url, name) {
+        Some(value) => match value.parse() {
+            Ok(n) => Ok(Some(n)),
+            Err(_) => reject(StatusCode::BadRequest, format!("Invalid {}", name)),
+        },
+        None => Ok(None),
+    }
+}
+
+fn ids_param(url: &Url, max: usize) -> Result<Vec<String>, Failure> {
+    let ids: Vec<String> = param(url, "ids")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|id| !id.is_empty())
+        .map(str::to_owned)
+        .collect();
+    if ids.is_empty() {
+        reject(StatusCode::BadRequest, "Missing ids")
+    } else if ids.len() > max {
+        reject(StatusCode::BadRequest, "Too many ids requested")
+    } else {
+        Ok(ids)
+    }
+}
+
+fn with_query(url: &Url, replace: &[(&str, String)]) -> String {
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| replace.iter().all(|(name, _)| k != name))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    let mut url = url.clone();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .extend_pairs(replace.iter().map(|(k, v)| (k, v)));
+    url.into()
+}
+
+/// Offset-based page of `items`, with `next` and `previous` links built from the request URL.
+fn page(
+    url: &Url,
+    items: Vec<Value>,
+    default_limit: usize,
+    max_limit: usize,
+) -> Result<Value, Failure> {
+    let limit = number_param(url, "limit")?.unwrap_or(default_limit);
+    let offset = number_param(url, "offset")?.unwrap_or(0);
+    if limit == 0 || limit > max_limit {
+        return reject(StatusCode::BadRequest, "Invalid limit");
+    }
+
+    let link = |offset: usize| {
+        with_query(
+            url,
+            &[("offset", offset.to_string()), ("limit", limit.to_string())],
+        )
+    };
+    let total = items.len();
+    let next = Some(offset + limit).filter(|&next| next < total).map(link);
+    let previous = Some(offset.saturating_sub(limit))
+        .filter(|_| offset > 0)
+        .map(link);
+    let items: Vec<Value> = items.into_iter().skip(offset).take(limit).collect();
+
+    Ok(json!({
+        "href": link(offset),
+        "items": items,
+        "limit": limit,
+        "next": next,
+        "offset": offset,
+        "previous": previous,
+        "total": total,
+    }))
+}
+
+/// A field selected by a `fields` expression, and the fields selected inside it, if any.
+#[derive(Debug)]
+struct Field {
+    name: String,
+    nested: Vec<Field>,
+}
+
+/// Parse a `fields` expression like `name,tracks.items(added_at,track(id,name))`. Exclusions
+/// with `!` aren't supported.
+fn parse_fields(expression: &str) -> Result<Vec<Field>, Failure> {
+    fn list(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<Vec<Field>, Failure> {
+        let mut fields = Vec::new();
+        loop {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if c == ',' || c == '(' || c == ')' || c == '.' {
+                    break;
+                }
+                name.push(c);
+                chars.next();
+            }
+            if name.is_empty() || name.starts_with('!') {
+                return reject(StatusCode::BadRequest, "Invalid fields");
+            }
+
+            let nested = match chars.peek() {
+                Some('(') => {
+                    chars.next();
+                    let nested = list(chars)?;
+                    if chars.next() != Some(')') {
+                        return reject(StatusCode::BadRequest, "Invalid fields");
+                    }
+                    nested
+                }
+                // `a.b` selects the same as `a(b)`
+                Some('.') => {
+                    chars.next();
+                    let mut nested = list(chars)?;
+                    let rest = nested.split_off(1);
+                    fields.push(Field { name, nested });
+                    fields.extend(rest);
+                    return Ok(fields);
+                }
+                _ => Vec::new(),
+            };
+            fields.push(Field { name, nested });
+
+            match chars.peek() {
+                Some(',') => {
+                    chars.next();
+                }
+                _ => return Ok(fields),
+            }
+        }
+    }
+
+    let mut chars = expression.chars().peekable();
+    let parsed = list(&mut chars)?;
+    match chars.next() {
+        None => Ok(parsed),
+        Some(_) => reject(StatusCode::BadRequest, "Invalid fields"),
+    }
+}
+
+fn select_fields(value: &Value, fields: &[Field]) -> Value {
+    match value {
+        Value::Array(values) => values.iter().map(|v| select_fields(v, fields)).collect(),
+        Value::Object(map) => {
+            let mut selected = serde_json::Map::new();
+            for field in fields {
+                let value = match map.get(&field.name) {
+                    Some(value) if field.nested.is_empty() => value.clone(),
+                    Some(value) => select_fields(value, &field.nested),
+                    None => continue,
+                };
+                // `a.b,a.c` selects both `b` and `c` from `a`
+                match (selected.get_mut(&field.name), value) {
+                    (Some(Value::Object(existing)), Value::Object(more)) => existing.extend(more),
+                    (_, value) => {
+                        selected.insert(field.name.clone(), value);
+                    }
+                }
+            }
+            Value::Object(selected)
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Apply the request's `fields` parameter to a response, as Spotify does for playlists.
+fn with_fields(url: &Url, value: Value) -> Reply {
+    match param(url, "fields") {
+        Some(expression) => ok(select_fields(&value, &parse_fields(&expression)?)),
+        None => ok(value),
+    }
+}
+
+fn body_field<'a>(body: &'a Value, name: &str) -> Result<&'a Value, Failure> {
+    match body.get(name) {
+        Some(value) => Ok(value),
+        None => reject(
+            StatusCode::BadRequest,
+            format!("Missing required field: {}", name),
+        ),
+    }
+}
+
+/// What a kind of item in the library looks like: its URI kind, the key of the item in a saved
+/// item object (audiobooks aren't wrapped) and how many IDs a request may carry.
+fn library_kind(kind: &str) -> Option<(&'static str, Option<&'static str>, usize)> {
+    match kind {
+        "albums" => Some(("album", Some("album"), 20)),
+        "audiobooks" => Some(("audiobook", None, 50)),
+        "episodes" => Some(("episode", Some("episode"), 50)),
+        "shows" => Some(("show", Some("show"), 50)),
+        "tracks" => Some(("track", Some("track"), 50)),
+        _ => None,
+    }
+}
+
+/// Catalog kinds that can be looked up by ID, and how many IDs a request may carry.
+fn catalog_kind(kind: &str) -> Option<(&'static str, usize)> {
+    match kind {
+        "albums" => Some(("album", 20)),
+        "artists" => Some(("artist", 50)),
+        "episodes" => Some(("episode", 50)),
+        "tracks" => Some(("track", 50)),
+        _ => None,
+    }
+}
+
+fn uri(kind: &str, id: &str) -> String {
+    format!("spotify:{}:{}", kind, id)
+}
+
+#[derive(Debug)]
+struct Saved {
+    uri: String,
+    added_at: SystemTime,
+}
+
+#[derive(Debug)]
+struct Item {
+    uri: String,
+    added_at: SystemTime,
+    added_by: String,
+}
+
+#[derive(Debug)]
+struct Playlist {
+    id: String,
+    name: String,
+    description: Option<String>,
+    public: Option<bool>,
+    collaborative: bool,
+    owner: String,
+    images: Value,
+    items: Vec<Item>,
+    version: u64,
+    followers: Vec<String>,
+}
+
+impl Playlist {
+    fn snapshot_id(&self) -> String {
+        format!("{}-{}", self.id, self.version)
+    }
+
+    fn changed(&mut self) -> Reply {
+        self.version += 1;
+        ok(json!({ "snapshot_id": self.snapshot_id() }))
+    }
+
+    fn href(&self) -> String {
+        format!("https://api.spotify.com/v1/playlists/{}", self.id)
+    }
+
+    fn can_edit(&self, user: &str) -> bool {
+        self.owner == user || self.collaborative
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    me: String,
+    // Everything that can be looked up, as Spotify would send it, by URI
+    catalog: HashMap<String, Value>,
+    // Most recently saved first, by kind as it appears in the path
+    library: HashMap<String, Vec<Saved>>,
+    followed_artists: Vec<String>,
+    followed_users: Vec<String>,
+    playlists: Vec<Playlist>,
+    created_playlists: u64,
+    currently_playing: Option<String>,
+    queue: Vec<String>,
+    // Most recently played first
+    history: Vec<Saved>,
+    failures: VecDeque<StatusCode>,
+    requests: Vec<String>,
+}
+
+impl State {
+    fn add<T: Serialize>(&mut self, item: &T) -> Value {
+        let value = to_value(item);
+        if let Some(uri) = value["uri"].as_str() {
+            self.catalog.insert(uri.to_owned(), value.clone());
+        }
+        value
+    }
+
+    fn lookup(&self, uri: &str) -> Result<&Value, Failure> {
+        match self.catalog.get(uri) {
+            Some(value) => Ok(value),
+            None => reject(
+                StatusCode::BadRequest,
+                format!("Payload contains a non-existing ID: {}", uri),
+            ),
+        }
+    }
+
+    fn user(&self, id: &str) -> Value {
+        self.catalog
+            .get(&uri("user", id))
+            .cloned()
+            .unwrap_or_else(|| {
+                json!({
+                    "display_name": id,
+                    "external_urls": { "spotify": format!("https://open.spotify.com/user/{}", id) },
+                    "href": format!("https://api.spotify.com/v1/users/{}", id),
+                    "id": id,
+                    "type": "user",
+                    "uri": uri("user", id),
+                })
+            })
+    }
+
+    fn playlist(&self, id: &str) -> Result<&Playlist, Failure> {
+        match self.playlists.iter().find(|p| p.id == id) {
+            // Other users' private playlists can't be seen
+            Some(p) if p.owner == self.me || p.public != Some(false) => Ok(p),
+            _ => not_found(),
+        }
+    }
+
+    fn editable_playlist(&mut self, id: &str) -> Result<&mut Playlist, Failure> {
+        self.playlist(id)?;
+        let me = self.me.clone();
+        // UNWRAP: Checked just above
+        let playlist = self.playlists.iter_mut().find(|p| p.id == id).unwrap();
+        if !playlist.can_edit(&me) {
+            return reject(StatusCode::Forbidden, "You cannot edit this playlist");
+        }
+        Ok(playlist)
+    }
+
+    fn item(&self, item: &Item) -> Value {
+        json!({
+            "added_at": timestamp(item.added_at),
+            "added_by": self.user(&item.added_by),
+            "is_local": false,
+            "track": self.catalog.get(&item.uri),
+        })
+    }
+
+    fn simplified_playlist(&self, p: &Playlist) -> Value {
+        let mut value = self.full_playlist_fields(p);
+        value["tracks"] = json!({
+            "href": format!("{}/tracks", p.href()),
+            "total": p.items.len(),
+        });
+        value
+    }
+
+    fn full_playlist_fields(&self, p: &Playlist) -> Value {
+        json!({
+            "collaborative": p.collaborative,
+            "description": p.description,
+            "external_urls": { "spotify": format!("https://open.spotify.com/playlist/{}", p.id) },
+            "href": p.href(),
+            "id": p.id,
+            "images": p.images,
+            "name": p.name,
+            "owner": self.user(&p.owner),
+            "public": p.public,
+            "snapshot_id": p.snapshot_id(),
+            "type": "playlist",
+            "uri": uri("playlist", &p.id),
+        })
+    }
+
+    fn full_playlist(&self, p: &Playlist) -> Result<Value, Failure> {
+        let mut value = self.full_playlist_fields(p);
+        // UNWRAP: Known-valid URL
+        let items_url = Url::parse(&format!("{}/tracks", p.href())).unwrap();
+        let items = p.items.iter().map(|i| self.item(i)).collect();
+        value["followers"] = json!({ "href": null, "total": p.followers.len() });
+        value["tracks"] = page(&items_url, items, 100, 100)?;
+        Ok(value)
+    }
+
+    fn handle(&mut self, method: Method, url: &Url, body: &Value) -> Reply {
+        let path = url.path().trim_start_matches("/v1/").to_owned();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        match (method, segments.as_slice()) {
+            (Method::Get, ["me", "following"]) => self.followed_artists(url),
+            (Method::Get, ["me", "following", "contains"]) => self.follows(url),
+            (Method::Put, ["me", "following"]) => self.follow(url, true),
+            (Method::Delete, ["me", "following"]) => self.follow(url, false),
+            (Method::Get, ["me", "player", "queue"]) => self.get_queue(),
+            (Method::Post, ["me", "player", "queue"]) => self.add_to_queue(url),
+            (Method::Get, ["me", "player", "recently-played"]) => self.recently_played(url),
+            (Method::Get, ["me", "playlists"]) => {
+                let me = self.me.clone();
+                let items = self
+                    .playlists
+                    .iter()
+                    .filter(|p| p.followers.contains(&me))
+                    .map(|p| self.simplified_playlist(p))
+                    .collect();
+                ok(page(url, items, 20, 50)?)
+            }
+            (Method::Get, ["me", kind]) => self.library(kind, url),
+            (Method::Get, ["me", kind, "contains"]) => self.library_contains(kind, url),
+            (Method::Put, ["me", kind]) => self.save(kind, url, true),
+            (Method::Delete, ["me", kind]) => self.save(kind, url, false),
+            (Method::Get, ["users", user, "playlists"]) => {
+                let items = self
+                    .playlists
+                    .iter()
+                    .filter(|p| p.owner == *user && self.playlist(&p.id).is_ok())
+                    .map(|p| self.simplified_playlist(p))
+                    .collect();
+                ok(page(url, items, 20, 50)?)
+            }
+            (Method::Post, ["users", user, "playlists"]) => self.create_playlist(user, body),
+            (Method::Get, ["playlists", id]) => {
+                with_fields(url, self.full_playlist(self.playlist(id)?)?)
+            }
+            (Method::Put, ["playlists", id]) => self.change_playlist(id, body),
+            (Method::Get, ["playlists", id, "tracks"]) => {
+                let playlist = self.playlist(id)?;
+                let items = playlist.items.iter().map(|i| self.item(i)).collect();
+                with_fields(url, page(url, items, 100, 100)?)
+            }
+            (Method::Post, ["playlists", id, "tracks"]) => self.add_items(id, body),
+            (Method::Put, ["playlists", id, "tracks"]) => match body.get("range_start") {
+                Some(_) => self.reorder_items(id, body),
+                None => self.replace_items(id, body),
+            },
+            (Method::Delete, ["playlists", id, "tracks"]) => self.remove_items(id, body),
+            (Method::Get, ["playlists", id, "images"]) => ok(self.playlist(id)?.images.clone()),
+            (Method::Put, ["playlists", id, "followers"]) => self.follow_playlist(id, true),
+            (Method::Delete, ["playlists", id, "followers"]) => self.follow_playlist(id, false),
+            (Method::Get, ["playlists", id, "followers", "contains"]) => {
+                let playlist = self.playlist(id)?;
+                let ids = ids_param(url, 5)?;
+                ok(json!(ids
+                    .iter()
+                    .map(|id| playlist.followers.contains(id))
+                    .collect::<Vec<_>>()))
+            }
+            (Method::Get, [kind]) => {
+                let (type_, max) = catalog_kind(kind).map_or_else(not_found, Ok)?;
+                let items: Vec<Option<&Value>> = ids_param(url, max)?
+                    .iter()
+                    .map(|id| self.catalog.get(&uri(type_, id)))
+                    .collect();
+                ok(json!({ *kind: items }))
+            }
+            (Method::Get, [kind, id]) => {
+                let (type_, _) = catalog_kind(kind).map_or_else(not_found, Ok)?;
+                match self.catalog.get(&uri(type_, id)) {
+                    Some(value) => ok(value.clone()),
+                    None => not_found(),
+                }
+            }
+            _ => reject(
+                StatusCode::NotFound,
+                format!("The fake API doesn't implement {} {}", method, url.path()),
+            ),
+        }
+    }
+
+    fn library(&self, kind: &str, url: &Url) -> Reply {
+        let (_, key, _) = library_kind(kind).map_or_else(not_found, Ok)?;
+        let saved = self
+            .library
+            .get(kind)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let items = saved
+            .iter()
+            .map(|s| {
+                let item = self.catalog[&s.uri].clone();
+                match key {
+                    Some(key) => json!({ "added_at": timestamp(s.added_at), key: item }),
+                    None => item,
+                }
+            })
+            .collect();
+        ok(page(url, items, 20, 50)?)
+    }
+
+    fn library_contains(&self, kind: &str, url: &Url) -> Reply {
+        let (type_, _, max) = library_kind(kind).map_or_else(not_found, Ok)?;
+        let saved = self
+            .library
+            .get(kind)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let contains: Vec<bool> = ids_param(url, max)?
+            .iter()
+            .map(|id| saved.iter().any(|s| s.uri == uri(type_, id)))
+            .collect();
+        ok(json!(contains))
+    }
+
+    fn save(&mut self, kind: &str, url: &Url, save: bool) -> Reply {
+        let (type_, _, max) = library_kind(kind).map_or_else(not_found, Ok)?;
+        let uris: Vec<String> = ids_param(url, max)?
+            .iter()
+            .map(|id| uri(type_, id))
+            .collect();
+        for uri in &uris {
+            self.lookup(uri)?;
+        }
+
+        let saved = self.library.entry(kind.to_owned()).or_default();
+        saved.retain(|s| !uris.contains(&s.uri));
+        if save {
+            let now = SystemTime::now();
+            for uri in uris {
+                saved.insert(0, Saved { uri, added_at: now });
+            }
+        }
+        ok(Value::Null)
+    }
+
+    fn followed(&mut self, url: &Url) -> Result<&mut Vec<String>, Failure> {
+        match param(url, "type").as_deref() {
+            Some("artist") => Ok(&mut self.followed_artists),
+            Some("user") => Ok(&mut self.followed_users),
+            _ => reject(StatusCode::BadRequest, "Invalid type"),
+        }
+    }
+
+    fn follows(&mut self, url: &Url) -> Reply {
+        let ids = ids_param(url, 50)?;
+        let followed = self.followed(url)?;
+        ok(json!(ids
+            .iter()
+            .map(|id| followed.contains(id))
+            .collect::<Vec<_>>()))
+    }
+
+    fn follow(&mut self, url: &Url, follow: bool) -> Reply {
+        let ids = ids_param(url, 50)?;
+        if param(url, "type").as_deref() == Some("artist") {
+            for id in &ids {
+                self.lookup(&uri("artist", id))?;
+            }
+        }
+
+        let followed = self.followed(url)?;
+        followed.retain(|id| !ids.contains(id));
+        if follow {
+            followed.extend(ids);
+        }
+        Ok((StatusCode::NoContent, Value::Null))
+    }
+
+    fn followed_artists(&self, url: &Url) -> Reply {
+        if param(url, "type").as_deref() != Some("artist") {
+            return reject(StatusCode::BadRequest, "Only artists can be listed");
+        }
+        let limit = number_param(url, "limit")?.unwrap_or(20);
+        if limit == 0 || limit > 50 {
+            return reject(StatusCode::BadRequest, "Invalid limit");
+        }
+        let start = match param(url, "after") {
+            Some(after) => match self.followed_artists.iter().position(|id| *id == after) {
+                Some(position) => position + 1,
+                None => self.followed_artists.len(),
+            },
+            None => 0,
+        };
+
+        let ids = &self.followed_artists[start..];
+        let shown = &ids[..limit.min(ids.len())];
+        let after = shown.last().filter(|_| ids.len() > limit);
+        let next = after.map(|after| with_query(url, &[("after", after.clone())]));
+        let items: Vec<&Value> = shown
+            .iter()
+            .map(|id| &self.catalog[&uri("artist", id)])
+            .collect();
+
+        ok(json!({
+            "artists": {
+                "href": url.as_str(),
+                "items": items,
+                "limit": limit,
+                "next": next,
+                "cursors": { "after": after, "before": null },
+                "total": self.followed_artists.len(),
+            }
+        }))
+    }
+
+    fn get_queue(&self) -> Reply {
+        let currently_playing = self
+            .currently_playing
+            .as_ref()
+            .and_then(|uri| self.catalog.get(uri));
+        let queue: Vec<&Value> = self.queue.iter().map(|uri| &self.catalog[uri]).collect();
+        ok(json!({ "currently_playing": currently_playing, "queue": queue }))
+    }
+
+    fn add_to_queue(&mut self, url: &Url) -> Reply {
+        let uri = param(url, "uri").unwrap_or_default();
+        let playable = uri.starts_with("spotify:track:") || uri.starts_with("spotify:episode:");
+        if !playable || !self.catalog.contains_key(&uri) {
+            return reject(StatusCode::BadRequest, format!("Invalid uri: {}", uri));
+        }
+        self.queue.push(uri);
+        Ok((StatusCode::NoContent, Value::Null))
+    }
+
+    fn recently_played(&self, url: &Url) -> Reply {
+        let limit = number_param(url, "limit")?.unwrap_or(20);
+        if limit == 0 || limit > 50 {
+            return reject(StatusCode::BadRequest, "Invalid limit");
+        }
+        let before = number_param(url, "before")?;
+        let after = number_param(url, "after")?;
+        if before.is_some() && after.is_some() {
+            return reject(
+                StatusCode::BadRequest,
+                "Only one of before and after is allowed",
+            );
+        }
+
+        let played: Vec<&Saved> = self
+            .history
+            .iter()
+            .filter(|s| before.iter().all(|&b| unix_millis(s.added_at) < b as u128))
+            .filter(|s| after.iter().all(|&a| unix_millis(s.added_at) > a as u128))
+            .collect();
+        let shown = &played[..limit.min(played.len())];
+        let millis = |s: &&Saved| unix_millis(s.added_at).to_string();
+        let oldest = shown.last().map(millis);
+        let next = oldest
+            .as_ref()
+            .filter(|_| played.len() > limit)
+            .map(|before| with_query(url, &[("before", before.clone())]));
+
+        let items: Vec<Value> = shown
+            .iter()
+            .map(|s| {
+                let mut track = self.catalog[&s.uri].clone();
+                // Play history only has simplified tracks
+                if let Some(track) = track.as_object_mut() {
+                    for key in &["album", "external_ids", "popularity"] {
+                        track.remove(*key);
+                    }
+                }
+                json!({ "track": track, "played_at": timestamp(s.added_at), "context": null })
+            })
+            .collect();
+
+        ok(json!({
+            "href": url.as_str(),
+            "items": items,
+            "limit": limit,
+            "next": next,
+            "cursors": { "after": shown.first().map(millis), "before": oldest },
+        }))
+    }
+
+    fn create_playlist(&mut self, user: &str, body: &Value) -> Reply {
+        if user != self.me {
+            return reject(
+                StatusCode::Forbidden,
+                "You cannot create a playlist for another user",
+            );
+        }
+        let name = body_field(body, "name")?.as_str().unwrap_or_default();
+        let public = body["public"].as_bool().unwrap_or(true);
+        let collaborative = body["collaborative"].as_bool().unwrap_or(false);
+        if public && collaborative {
+            return reject(
+                StatusCode::BadRequest,
+                "Collaborative playlists can only be private",
+            );
+        }
+
+        self.created_playlists += 1;
+        let playlist = Playlist {
+            id: format!("{:0>22}", self.created_playlists),
+            name: name.to_owned(),
+            description: body["description"].as_str().map(str::to_owned),
+            public: Some(public),
+            collaborative,
+            owner: self.me.clone(),
+            images: json!([]),
+            items: Vec::new(),
+            version: 1,
+            followers: vec![self.me.clone()],
+        };
+        let value = self.full_playlist(&playlist)?;
+        self.playlists.push(playlist);
+        Ok((StatusCode::Created, value))
+    }
+
+    fn change_playlist(&mut self, id: &str, body: &Value) -> Reply {
+        let me = self.me.clone();
+        let playlist = self.editable_playlist(id)?;
+        if playlist.owner != me {
+            return reject(StatusCode::Forbidden, "Only the owner can change details");
+        }
+
+        let public = body["public"].as_bool().or(playlist.public);
+        let collaborative = body["collaborative"]
+            .as_bool()
+            .unwrap_or(playlist.collaborative);
+        if public == Some(true) && collaborative {
+            return reject(
+                StatusCode::BadRequest,
+                "Collaborative playlists can only be private",
+            );
+        }
+        if let Some(name) = body["name"].as_str() {
+            playlist.name = name.to_owned();
+        }
+        if let Some(description) = body["description"].as_str() {
+            playlist.description = Some(description.to_owned());
+        }
+        playlist.public = public;
+        playlist.collaborative = collaborative;
+        playlist.version += 1;
+        ok(Value::Null)
+    }
+
+    fn playable_uris(&self, body: &Value) -> Result<Vec<String>, Failure> {
+        let uris: Vec<String> = body_field(body, "uris")?
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|uri| uri.as_str().map(str::to_owned))
+            .collect();
+        if uris.len() > 100 {
+            return reject(StatusCode::BadRequest, "Too many ids requested");
+        }
+        for uri in &uris {
+            self.lookup(uri)?;
+        }
+        Ok(uris)
+    }
+
+    fn add_items(&mut self, id: &str, body: &Value) -> Reply {
+        let uris = self.playable_uris(body)?;
+        if uris.is_empty() {
+            return reject(StatusCode::BadRequest, "No uris provided");
+        }
+        let me = self.me.clone();
+        let playlist = self.editable_playlist(id)?;
+        let position = body["position"]
+            .as_u64()
+            .map_or(playlist.items.len(), |p| p as usize);
+        if position > playlist.items.len() {
+            return reject(StatusCode::BadRequest, "Index out of bounds");
+        }
+
+        let now = SystemTime::now();
+        let items = uris.into_iter().map(|uri| Item {
+            uri,
+            added_at: now,
+            added_by: me.clone(),
+        });
+        playlist.items.splice(position..position, items);
+        let (_, value) = playlist.changed()?;
+        Ok((StatusCode::Created, value))
+    }
+
+    fn replace_items(&mut self, id: &str, body: &Value) -> Reply {
+        let uris = self.playable_uris(body)?;
+        let me = self.me.clone();
+        let playlist = self.editable_playlist(id)?;
+
+        let now = SystemTime::now();
+        playlist.items = uris
+            .into_iter()
+            .map(|uri| Item {
+                uri,
+                added_at: now,
+                added_by: me.clone(),
+            })
+            .collect();
+        playlist.changed()
+    }
+
+    fn reorder_items(&mut self, id: &str, body: &Value) -> Reply {
+        let playlist = self.editable_playlist(id)?;
+        check_snapshot(playlist, body)?;
+
+        let number = |name: &str| body[name].as_u64().map(|n| n as usize);
+        let len = playlist.items.len();
+        let start = number("range_start").unwrap_or_default();
+        let length = number("range_length").unwrap_or(1);
+        let end = start.checked_add(length).filter(|&end| end <= len);
+        let (insert_before, end) = match (number("insert_before"), end) {
+            (Some(i), Some(end)) if i <= len => (i, end),
+            _ => return reject(StatusCode::BadRequest, "Index out of bounds"),
+        };
+
+        let moved: Vec<Item> = playlist.items.drain(start..end).collect();
+        let at = match insert_before > start {
+            true => insert_before.saturating_sub(length).max(start),
+            false => insert_before,
+        };
+        playlist.items.splice(at..at, moved);
+        playlist.changed()
+    }
+
+    fn remove_items(&mut self, id: &str, body: &Value) -> Reply {
+        let playlist = self.editable_playlist(id)?;
+        check_snapshot(playlist, body)?;
+
+        let tracks = body_field(body, "tracks")?.as_array();
+        if tracks.iter().all(|t| t.is_empty()) {
+            return reject(StatusCode::BadRequest, "No tracks provided");
+        }
+
+        let mut remove = vec![false; playlist.items.len()];
+        for entry in tracks.into_iter().flatten() {
+            let uri = entry["uri"].as_str().unwrap_or_default();
+            match entry["positions"].as_array() {
+                Some(positions) => {
+                    for position in positions {
+                        let position = position.as_u64().unwrap_or(u64::MAX) as usize;
+                        match playlist.items.get(position) {
+                            Some(item) if item.uri == uri => remove[position] = true,
+                            _ => {
+                                return reject(
+                                    StatusCode::BadRequest,
+                                    format!("{} is not at position {}", uri, position),
+                                )
+                            }
+                        }
+                    }
+                }
+                None => {
+                    for (item, remove) in playlist.items.iter().zip(remove.iter_mut()) {
+                        *remove |= item.uri == uri;
+                    }
+                }
+            }
+        }
+
+        let mut remove = remove.into_iter();
+        playlist.items.retain(|_| !remove.next().unwrap_or(false));
+        playlist.changed()
+    }
+
+    fn follow_playlist(&mut self, id: &str, follow: bool) -> Reply {
+        self.playlist(id)?;
+        let me = self.me.clone();
+        // UNWRAP: Checked just above
+        let playlist = self.playlists.iter_mut().find(|p| p.id == id).unwrap();
+        playlist.followers.retain(|user| *user != me);
+        if follow {
+            playlist.followers.push(me);
+        }
+        ok(Value::Null)
+    }
+}
+
+// Positions only mean something for the version of the playlist they were taken from
+fn check_snapshot(playlist: &Playlist, body: &Value) -> Result<(), Failure> {
+    match body["snapshot_id"].as_str() {
+        Some(snapshot_id) if snapshot_id != playlist.snapshot_id() => reject(
+            StatusCode::BadRequest,
+            "The playlist has changed since this snapshot",
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// A fake Spotify Web API that lives in memory and serves requests without a network.
+///
+/// The library, follow, playlist and player (queue and play history) endpoints work against
+/// state that changes with every request, so a track saved through the fake shows up in the
+/// next read of the library. Albums, artists, episodes and tracks in the catalog can also be
+/// looked up by ID. Everything has to be seeded first with the `add_*` methods, for example
+/// from models deserialized out of recorded responses.
+///
+/// Pages are cut with `limit` and `offset` (or cursors) and link to the next page the way
+/// Spotify does, and playlist reads honor `fields`. Unknown IDs and playlists answer `400` or
+/// `404`, and [`fail_next`] makes requests fail with any other status, like `429` or `503`.
+///
+/// The fake is cheap to clone, and clones share their state. Requests aren't checked for a
+/// token, except that unauthorized requests always fail with `401`.
+///
+/// [`fail_next`]: FakeSpotify::fail_next
+#[derive(Clone, Debug)]
+pub struct FakeSpotify {
+    state: Arc<Mutex<State>>,
+}
+
+impl FakeSpotify {
+    /// An empty API on behalf of `me`, the current user.
+    pub fn new(me: PublicUser) -> Self {
+        let mut state = State {
+            me: me.id.to_string(),
+            catalog: HashMap::new(),
+            library: HashMap::new(),
+            followed_artists: Vec::new(),
+            followed_users: Vec::new(),
+            playlists: Vec::new(),
+            created_playlists: 0,
+            currently_playing: None,
+            queue: Vec::new(),
+            history: Vec::new(),
+            failures: VecDeque::new(),
+            requests: Vec::new(),
+        };
+        state.add(&me);
+        FakeSpotify {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut State) -> T) -> T {
+        // UNWRAP: The lock is never held across a panic
+        f(&mut self.state.lock().unwrap())
+    }
+
+    pub fn add_album(&self, album: FullAlbum) {
+        self.with_state(|s| s.add(&album));
+    }
+
+    pub fn add_artist(&self, artist: FullArtist) {
+        self.with_state(|s| s.add(&artist));
+    }
+
+    pub fn add_audiobook(&self, audiobook: SimplifiedAudiobook) {
+        self.with_state(|s| s.add(&audiobook));
+    }
+
+    pub fn add_episode(&self, episode: FullEpisode) {
+        self.with_state(|s| s.add(&episode));
+    }
+
+    pub fn add_show(&self, show: SimplifiedShow) {
+        self.with_state(|s| s.add(&show));
+    }
+
+    pub fn add_track(&self, track: FullTrack) {
+        self.with_state(|s| s.add(&track));
+    }
+
+    pub fn add_user(&self, user: PublicUser) {
+        self.with_state(|s| s.add(&user));
+    }
+
+    /// Add a playlist along with its owner and the tracks on its first page of items. The
+    /// current user follows the playlists they own.
+    pub fn add_playlist(&self, playlist: FullPlaylist) {
+        self.with_state(|s| {
+            let owner = playlist.owner.id.to_string();
+            s.add(&playlist.owner);
+            let items = playlist
+                .tracks
+                .items
+                .iter()
+                .filter_map(|item| {
+                    let track = item.track.as_ref()?;
+                    let added_by = item.added_by.as_ref().map(|u| s.add(u));
+                    Some(Item {
+                        uri: s.add(track)["uri"].as_str()?.to_owned(),
+                        added_at: item.added_at.system_time(),
+                        added_by: added_by
+                            .and_then(|u| u["id"].as_str().map(str::to_owned))
+                            .unwrap_or_else(|| owner.clone()),
+                    })
+                })
+                .collect();
+            let followers = if owner == s.me {
+                vec![owner.clone()]
+            } else {
+                Vec::new()
+            };
+
+            s.playlists.retain(|p| p.id != playlist.id.to_string());
+            s.playlists.push(Playlist {
+                id: playlist.id.to_string(),
+                name: playlist.name,
+                description: playlist.description,
+                public: playlist.public,
+                collaborative: playlist.collaborative,
+                owner,
+                images: to_value(&playlist.images),
+                items,
+                version: 1,
+                followers,
+            });
+        });
+    }
+
+    /// Add a track to the catalog and to the play history.
+    pub fn add_played(&self, track: FullTrack, played_at: SystemTime) {
+        self.with_state(|s| {
+            let played = Saved {
+                uri: s.add(&track)["uri"].as_str().unwrap_or_default().to_owned(),
+                added_at: played_at,
+            };
+            let position = s.history.iter().position(|p| p.added_at < played_at);
+            s.history
+                .insert(position.unwrap_or(s.history.len()), played);
+        });
+    }
+
+    /// Set the track or episode the queue starts with, by URI.
+    pub fn set_currently_playing(&self, uri: Option<&str>) {
+        self.with_state(|s| s.currently_playing = uri.map(str::to_owned));
+    }
+
+    /// Answer the next request with an error with this status instead of handling it. Calling
+    /// this several times fails that many requests, in order. A `429 Too Many Requests` carries
+    /// a `Retry-After` header, like Spotify's.
+    pub fn fail_next(&self, status: StatusCode) {
+        self.with_state(|s| s.failures.push_back(status));
+    }
+
+    /// Every request received so far, as the method followed by the path and query.
+    pub fn requests(&self) -> Vec<String> {
+        self.with_state(|s| s.requests.clone())
+    }
+}
+
+fn response(status: StatusCode, body: Value) -> Response {
+    let mut resp = Response::new(status);
+    if !body.is_null() {
+        resp.set_body(body);
+    }
+    resp
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    let error = json!({ "status": status as u16, "message": message });
+    response(status, json!({ "error": error }))
+}
+
+impl HttpClient for FakeSpotify {
+    fn send(&self, _req: http_client::Request) -> HttpClientFuture {
+        let resp = error_response(StatusCode::Unauthorized, "No token provided");
+        Box::pin(async move { Ok(resp) })
+    }
+}
+
+impl SpotifyClient for FakeSpotify {
+    fn send_authorized(&self, mut req: http_client::Request) -> HttpClientFuture {
+        let state = Arc::clone(&self.state);
+        Box::pin(async move {
+            let body = req.body_string().await?;
+            let url = req.url().clone();
+
+            // UNWRAP: The lock is never held across a panic
+            let mut state = state.lock().unwrap();
+            state
+                .requests
+                .push(format!("{} {}", req.method(), &url[Position::BeforePath..]));
+
+            if let Some(status) = state.failures.pop_front() {
+                let mut resp = error_response(status, status.canonical_reason());
+                if status == StatusCode::TooManyRequests {
+                    resp.insert_header(RETRY_AFTER, "1");
+                }
+                return Ok(resp);
+            }
+
+            let body = match body.as_str() {
+                "" => Value::Null,
+                body => match serde_json::from_str(body) {
+                    Ok(body) => body,
+                    Err(_) => {
+                        return Ok(error_response(StatusCode::BadRequest, "Error parsing JSON"))
+                    }
+                },
+            };
+            Ok(match state.handle(req.method(), &url, &body) {
+                Ok((status, body)) => response(status, body),
+                Err((status, message)) => error_response(status, &message),
+            })
+        })
+    }
+}
@@ -1,90 +1,99 @@
+use crate::api::chunks::{chunked, for_each_chunk};
 use crate::api::client::{ClientExt, SpotifyClient};
-use crate::model::artist::FollowedArtists;
+use crate::api::pager::{Pageable, Pager};
+use crate::model::artist::{FollowedArtists, FullArtist};
+use crate::model::id::{ArtistId, Id, PlaylistId, UserId};
 use crate::Result;
 use http_types::{Method, Request, Url};
-use std::borrow::Borrow;
 
-async fn user_follows<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+// Spotify accepts at most 50 IDs per request, so larger requests are split up
+const MAX_FOLLOW_IDS: usize = 50;
+
+async fn user_follows<C: SpotifyClient + ?Sized, I: Id>(
     client: &C,
     type_: &str,
-    ids: &[B],
+    ids: &[I],
 ) -> Result<Vec<bool>> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/following/contains").unwrap();
+    chunked(ids, MAX_FOLLOW_IDS, |ids| {
+        // UNWRAP: Known-valid URL
+        let mut url = Url::parse("https://api.spotify.com/v1/me/following/contains").unwrap();
 
-    url.set_query(Some(type_));
-    set_query_param_joined!(url, ids);
+        url.set_query(Some(type_));
+        set_query_param_joined!(url, ids);
 
-    let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+        let req = Request::new(Method::Get, url);
+        client.send_authorized(req).deserialize_response()
+    })
+    .await
 }
 
-pub async fn user_follows_artists<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn user_follows_artists<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[ArtistId],
 ) -> Result<Vec<bool>> {
     user_follows(client, "type=artist", ids).await
 }
 
-pub async fn user_follows_users<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn user_follows_users<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[UserId],
 ) -> Result<Vec<bool>> {
     user_follows(client, "type=user", ids).await
 }
 
-pub async fn users_follow_playlist<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+const MAX_PLAYLIST_FOLLOWER_IDS: usize = 5;
+
+pub async fn users_follow_playlist<C: SpotifyClient + ?Sized>(
     client: &C,
-    playlist_id: &str,
-    user_ids: &[B],
+    playlist_id: &PlaylistId,
+    user_ids: &[UserId],
 ) -> Result<Vec<bool>> {
     let url = format!(
         "https://api.spotify.com/v1/playlists/{}/followers/contains",
         playlist_id
     );
-    let mut url = Url::parse(&url)?;
+    let url = Url::parse(&url)?;
 
-    let ids = user_ids;
-    set_query_param_joined!(url, ids);
+    chunked(user_ids, MAX_PLAYLIST_FOLLOWER_IDS, |ids| {
+        let mut url = url.clone();
+        set_query_param_joined!(url, ids);
 
-    let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+        let req = Request::new(Method::Get, url);
+        client.send_authorized(req).deserialize_response()
+    })
+    .await
 }
 
-async fn modify_follows<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+async fn modify_follows<C: SpotifyClient + ?Sized, I: Id>(
     client: &C,
     method: Method,
     type_: &str,
-    ids: &[B],
+    ids: &[I],
 ) -> Result<()> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/following").unwrap();
+    for_each_chunk(ids, MAX_FOLLOW_IDS, |ids| {
+        // UNWRAP: Known-valid URL
+        let mut url = Url::parse("https://api.spotify.com/v1/me/following").unwrap();
 
-    url.set_query(Some(type_));
-    set_query_param_joined!(url, ids);
+        url.set_query(Some(type_));
+        set_query_param_joined!(url, ids);
 
-    let req = Request::new(method, url);
-    client.send_authorized(req).await?;
-    Ok(())
+        let req = Request::new(method, url);
+        client.send_authorized(req).expect_success()
+    })
+    .await
 }
 
-pub async fn follow_artists<C: SpotifyClient + ?Sized, B: Borrow<str>>(
-    client: &C,
-    ids: &[B],
-) -> Result<()> {
+pub async fn follow_artists<C: SpotifyClient + ?Sized>(client: &C, ids: &[ArtistId]) -> Result<()> {
     modify_follows(client, Method::Put, "type=artist", ids).await
 }
 
-pub async fn follow_users<C: SpotifyClient + ?Sized, B: Borrow<str>>(
-    client: &C,
-    ids: &[B],
-) -> Result<()> {
+pub async fn follow_users<C: SpotifyClient + ?Sized>(client: &C, ids: &[UserId]) -> Result<()> {
     modify_follows(client, Method::Put, "type=user", ids).await
 }
 
 pub async fn follow_playlist_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    playlist_id: &str,
+    playlist_id: &PlaylistId,
     public: Option<bool>,
 ) -> Result<()> {
     let url = format!(
@@ -96,28 +105,47 @@ pub async fn follow_playlist_with_options<C: SpotifyClient + ?Sized>(
     set_query_param!(url, public);
 
     let req = Request::new(Method::Put, url);
-    client.send_authorized(req).await?;
-    Ok(())
+    client.send_authorized(req).expect_success().await
 }
 
 pub async fn follow_playlist<C: SpotifyClient + ?Sized>(
     client: &C,
-    playlist_id: &str,
+    playlist_id: &PlaylistId,
 ) -> Result<()> {
     follow_playlist_with_options(client, playlist_id, None).await
 }
 
-pub async fn user_followed_artists_with_options<C: SpotifyClient + ?Sized>(
+pub async fn unfollow_playlist<C: SpotifyClient + ?Sized>(
     client: &C,
-    limit: Option<usize>,
-    after: Option<&str>,
-) -> Result<FollowedArtists> {
+    playlist_id: &PlaylistId,
+) -> Result<()> {
+    let url = format!(
+        "https://api.spotify.com/v1/playlists/{}/followers",
+        playlist_id
+    );
+    let url = Url::parse(&url)?;
+
+    let req = Request::new(Method::Delete, url);
+    client.send_authorized(req).expect_success().await
+}
+
+fn followed_artists_url(limit: Option<usize>, after: Option<&str>) -> Url {
     // UNWRAP: Known-valid URL
     let mut url = Url::parse("https://api.spotify.com/v1/me/following?type=artist").unwrap();
 
     set_query_param!(url, limit);
     set_query_param!(url, after);
 
+    url
+}
+
+pub async fn user_followed_artists_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+    after: Option<&str>,
+) -> Result<FollowedArtists> {
+    let url = followed_artists_url(limit, after);
+
     let req = Request::new(Method::Get, url);
     client.send_authorized(req).deserialize_response().await
 }
@@ -128,16 +156,59 @@ pub async fn user_followed_artists<C: SpotifyClient + ?Sized>(
     user_followed_artists_with_options(client, None, None).await
 }
 
-pub async fn unfollow_artists<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn unfollow_artists<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[ArtistId],
 ) -> Result<()> {
     modify_follows(client, Method::Delete, "type=artist", ids).await
 }
 
-pub async fn unfollow_users<C: SpotifyClient + ?Sized, B: Borrow<str>>(
-    client: &C,
-    ids: &[B],
-) -> Result<()> {
+pub async fn unfollow_users<C: SpotifyClient + ?Sized>(client: &C, ids: &[UserId]) -> Result<()> {
     modify_follows(client, Method::Delete, "type=user", ids).await
 }
+
+/// Page of followed artists whose next page is found by following the `after` cursor. Unlike
+/// `FollowedArtists`, this can be used with `Pager` because it handles the response envelope.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(from = "FollowedArtists")]
+pub struct FollowedArtistsPage {
+    items: Vec<FullArtist>,
+    next: Option<String>,
+}
+
+impl From<FollowedArtists> for FollowedArtistsPage {
+    fn from(followed: FollowedArtists) -> Self {
+        let page = followed.artists;
+        let limit = Some(page.limit as usize);
+        let has_items = !page.items.is_empty();
+        let next = page
+            .cursors
+            .after
+            .filter(|_| has_items)
+            .map(|after| followed_artists_url(limit, Some(&after)).into());
+
+        FollowedArtistsPage {
+            items: page.items,
+            next,
+        }
+    }
+}
+
+impl Pageable<FullArtist> for FollowedArtistsPage {
+    fn next_url(&self) -> Option<&str> {
+        self.next.as_deref()
+    }
+
+    fn into_items(self) -> Vec<FullArtist> {
+        self.items
+    }
+}
+
+/// Stream every artist the current user follows, fetching `limit` artists per request.
+pub fn user_followed_artists_stream<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+) -> Pager<'_, C, FullArtist, FollowedArtistsPage> {
+    let url = followed_artists_url(limit, None);
+    Pager::with_items(client, Vec::new(), Some(url))
+}
@@ -1,50 +1,92 @@
+use crate::api::chunks::{chunked, for_each_chunk};
 use crate::client::{ClientExt, SpotifyClient};
 use crate::model::album::SavedAlbum;
+use crate::model::audiobook::SimplifiedAudiobook;
+use crate::model::id::{AlbumId, AudiobookId, EpisodeId, Id, ShowId, TrackId};
 use crate::model::page::Page;
-use crate::model::show::Show;
+use crate::model::show::{SavedEpisode, Show};
 use crate::model::track::SavedTrack;
 use crate::Result;
 
 use http_types::{Method, Request, Url};
-use std::borrow::Borrow;
 
-pub async fn library_contains_albums<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+// Spotify accepts at most 20 album IDs or 50 IDs of other types per request, so larger requests
+// are split up
+const MAX_ALBUM_IDS: usize = 20;
+const MAX_IDS: usize = 50;
+
+async fn library_contains<C: SpotifyClient + ?Sized, I: Id>(
     client: &C,
-    ids: &[B],
+    type_: &str,
+    max_ids: usize,
+    ids: &[I],
 ) -> Result<Vec<bool>> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/albums/contains").unwrap();
+    let url = format!("https://api.spotify.com/v1/me/{}/contains", type_);
+    let url = Url::parse(&url)?;
 
-    set_query_param_joined!(url, ids);
+    chunked(ids, max_ids, |ids| {
+        let mut url = url.clone();
+        set_query_param_joined!(url, ids);
 
-    let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+        let req = Request::new(Method::Get, url);
+        client.send_authorized(req).deserialize_response()
+    })
+    .await
 }
 
-pub async fn library_contains_shows<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+async fn modify_library<C: SpotifyClient + ?Sized, I: Id>(
     client: &C,
-    ids: &[B],
-) -> Result<Vec<bool>> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/shows/contains").unwrap();
+    method: Method,
+    type_: &str,
+    max_ids: usize,
+    ids: &[I],
+) -> Result<()> {
+    let url = format!("https://api.spotify.com/v1/me/{}", type_);
+    let url = Url::parse(&url)?;
 
-    set_query_param_joined!(url, ids);
+    for_each_chunk(ids, max_ids, |ids| {
+        let mut url = url.clone();
+        set_query_param_joined!(url, ids);
 
-    let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+        let req = Request::new(method, url);
+        client.send_authorized(req).expect_success()
+    })
+    .await
 }
 
-pub async fn library_contains_tracks<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn library_contains_albums<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[AlbumId],
 ) -> Result<Vec<bool>> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/tracks/contains").unwrap();
+    library_contains(client, "albums", MAX_ALBUM_IDS, ids).await
+}
 
-    set_query_param_joined!(url, ids);
+pub async fn library_contains_audiobooks<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[AudiobookId],
+) -> Result<Vec<bool>> {
+    library_contains(client, "audiobooks", MAX_IDS, ids).await
+}
 
-    let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+pub async fn library_contains_episodes<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[EpisodeId],
+) -> Result<Vec<bool>> {
+    library_contains(client, "episodes", MAX_IDS, ids).await
+}
+
+pub async fn library_contains_shows<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[ShowId],
+) -> Result<Vec<bool>> {
+    library_contains(client, "shows", MAX_IDS, ids).await
+}
+
+pub async fn library_contains_tracks<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[TrackId],
+) -> Result<Vec<bool>> {
+    library_contains(client, "tracks", MAX_IDS, ids).await
 }
 
 pub async fn library_get_albums_with_options<C: SpotifyClient + ?Sized>(
@@ -69,6 +111,50 @@ pub async fn library_get_albums<C: SpotifyClient + ?Sized>(client: &C) -> Result
     library_get_albums_with_options(client, None, None, None).await
 }
 
+pub async fn library_get_audiobooks_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Page<SimplifiedAudiobook>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/me/audiobooks").unwrap();
+
+    set_query_param!(url, limit);
+    set_query_param!(url, offset);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn library_get_audiobooks<C: SpotifyClient + ?Sized>(
+    client: &C,
+) -> Result<Page<SimplifiedAudiobook>> {
+    library_get_audiobooks_with_options(client, None, None).await
+}
+
+pub async fn library_get_episodes_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    market: Option<&str>,
+) -> Result<Page<SavedEpisode>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/me/episodes").unwrap();
+
+    set_query_param!(url, limit);
+    set_query_param!(url, offset);
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn library_get_episodes<C: SpotifyClient + ?Sized>(
+    client: &C,
+) -> Result<Page<SavedEpisode>> {
+    library_get_episodes_with_options(client, None, None, None).await
+}
+
 pub async fn library_get_shows_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
     limit: Option<usize>,
@@ -109,86 +195,72 @@ pub async fn library_get_tracks<C: SpotifyClient + ?Sized>(client: &C) -> Result
     library_get_tracks_with_options(client, None, None, None).await
 }
 
-pub async fn library_remove_albums<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn library_remove_albums<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[AlbumId],
 ) -> Result<()> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/albums").unwrap();
-
-    set_query_param_joined!(url, ids);
-
-    let req = Request::new(Method::Delete, url);
-    client.send_authorized(req).await?;
-    Ok(())
+    modify_library(client, Method::Delete, "albums", MAX_ALBUM_IDS, ids).await
 }
 
-pub async fn library_remove_shows<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn library_remove_audiobooks<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[AudiobookId],
 ) -> Result<()> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/shows").unwrap();
-
-    set_query_param_joined!(url, ids);
-
-    let req = Request::new(Method::Delete, url);
-    client.send_authorized(req).await?;
-    Ok(())
+    modify_library(client, Method::Delete, "audiobooks", MAX_IDS, ids).await
 }
 
-pub async fn library_remove_tracks<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn library_remove_episodes<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[EpisodeId],
 ) -> Result<()> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/tracks").unwrap();
-
-    set_query_param_joined!(url, ids);
-
-    let req = Request::new(Method::Delete, url);
-    client.send_authorized(req).await?;
-    Ok(())
+    modify_library(client, Method::Delete, "episodes", MAX_IDS, ids).await
 }
 
-pub async fn library_save_albums<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn library_remove_shows<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[ShowId],
 ) -> Result<()> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/albums").unwrap();
-
-    set_query_param_joined!(url, ids);
-
-    let req = Request::new(Method::Put, url);
-    client.send_authorized(req).await?;
-    Ok(())
+    modify_library(client, Method::Delete, "shows", MAX_IDS, ids).await
 }
 
-pub async fn library_save_shows<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn library_remove_tracks<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[TrackId],
 ) -> Result<()> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/shows").unwrap();
+    modify_library(client, Method::Delete, "tracks", MAX_IDS, ids).await
+}
 
-    set_query_param_joined!(url, ids);
+pub async fn library_save_albums<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[AlbumId],
+) -> Result<()> {
+    modify_library(client, Method::Put, "albums", MAX_ALBUM_IDS, ids).await
+}
 
-    let req = Request::new(Method::Put, url);
-    client.send_authorized(req).await?;
-    Ok(())
+pub async fn library_save_audiobooks<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[AudiobookId],
+) -> Result<()> {
+    modify_library(client, Method::Put, "audiobooks", MAX_IDS, ids).await
 }
 
-pub async fn library_save_tracks<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn library_save_episodes<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[EpisodeId],
 ) -> Result<()> {
-    // UNWRAP: Known-valid URL
-    let mut url = Url::parse("https://api.spotify.com/v1/me/tracks").unwrap();
+    modify_library(client, Method::Put, "episodes", MAX_IDS, ids).await
+}
 
-    set_query_param_joined!(url, ids);
+pub async fn library_save_shows<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[ShowId],
+) -> Result<()> {
+    modify_library(client, Method::Put, "shows", MAX_IDS, ids).await
+}
 
-    let req = Request::new(Method::Put, url);
-    client.send_authorized(req).await?;
-    Ok(())
+pub async fn library_save_tracks<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[TrackId],
+) -> Result<()> {
+    modify_library(client, Method::Put, "tracks", MAX_IDS, ids).await
 }
@@ -0,0 +1,15 @@
+use crate::api::client::{ClientExt, SpotifyClient};
+use crate::model::market::Markets;
+use crate::Result;
+use http_types::{Method, Request, Url};
+
+/// Get the list of markets where Spotify is available, as ISO 3166-1 alpha-2 codes.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/markets/get-available-markets/)
+pub async fn markets<C: SpotifyClient + ?Sized>(client: &C) -> Result<Markets> {
+    // UNWRAP: Known-valid URL
+    let url = Url::parse("https://api.spotify.com/v1/markets").unwrap();
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
@@ -1,6 +1,8 @@
+use std::sync::Arc;
 use thiserror::Error;
 
-// Set a URL parameter using the parameter name as its value.
+// Set a URL parameter using the parameter name as its value. Parameters are appended, so setting
+// several parameters on the same URL is safe.
 //
 // ```rust
 // use http_types::Url;
@@ -12,7 +14,8 @@ use thiserror::Error;
 macro_rules! set_query_param {
     ($url:expr, $param:ident) => {
         if let Some($param) = $param {
-            $url.set_query(Some(&format!(concat!(stringify!($param), "={}"), $param)))
+            $url.query_pairs_mut()
+                .append_pair(stringify!($param), &$param.to_string());
         }
     };
 }
@@ -24,25 +27,39 @@ macro_rules! set_query_param_joined {
     ($url:expr, $param:ident) => {
         if !$param.is_empty() {
             let value = $param.join(",");
-            $url.set_query(Some(&format!(concat!(stringify!($param), "={}"), value)))
+            $url.query_pairs_mut()
+                .append_pair(stringify!($param), &value);
         }
     };
 }
 
 pub mod albums;
 pub mod artists;
+pub mod audiobooks;
 pub mod auth;
+#[cfg(feature = "batch")]
+pub mod batch;
 pub mod browse;
+pub mod cache;
+pub mod chapters;
+mod chunks;
 pub mod client;
+pub mod dedup;
 pub mod episodes;
+#[cfg(feature = "testing")]
+pub mod fake;
 pub mod follow;
 pub mod library;
+pub mod markets;
 pub mod pager;
 pub mod personalization;
 pub mod player;
 pub mod playlists;
+pub mod raw;
 pub mod search;
 pub mod shows;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod tracks;
 pub mod users;
 
@@ -56,11 +73,34 @@ pub enum Error {
     #[error("")]
     Http(http_client::Error),
 
+    #[error("Spotify responded with {status}: {message}")]
+    Api {
+        status: http_types::StatusCode,
+        message: String,
+    },
+
     #[error("client was asked to send an authorized request, but no token was available")]
     MissingToken,
 
     #[error("")]
     Url(#[from] http_types::url::ParseError),
+
+    #[error("recommendations need between 1 and 5 seeds in total, but {0} were provided")]
+    SeedCount(usize),
+
+    #[error("{0} is not an available genre seed")]
+    UnknownGenreSeed(String),
+
+    #[error("cover image must be a JPEG")]
+    NotJpeg,
+
+    #[error("cover image is {0} bytes once encoded, but at most 256 KB is allowed")]
+    ImageTooLarge(usize),
+
+    /// A request made on behalf of several callers failed; each of them gets a handle to the
+    /// same error
+    #[error("{0}")]
+    Shared(Arc<Error>),
 }
 
 impl From<http_client::Error> for Error {
@@ -5,7 +5,6 @@ use futures::ready;
 use futures::stream::Stream;
 use http_types::{Method, Request, Url};
 use serde::de::DeserializeOwned;
-use std::mem::swap;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -45,7 +44,7 @@ where
             }
 
             // Queue all items, and fall through to returning them individually
-            swap(items, &mut page.into_items());
+            *items = page.into_items();
         }
 
         // Return the next item if there are any available
@@ -1,22 +1,24 @@
 use crate::api::client::{ClientExt, SpotifyClient};
-use crate::model::album::FullAlbum;
+use crate::api::pager::Pager;
+use crate::model::artist::FullArtist;
 use crate::model::page::Page;
-use crate::model::senum::TimeRange;
+use crate::model::senum::{TimeRange, TopItemType};
 use crate::model::track::FullTrack;
 use crate::Result;
 
 use http_types::{Method, Request, Url};
 use serde::de::DeserializeOwned;
 
-async fn top_recommendations<C: SpotifyClient + ?Sized, T: DeserializeOwned>(
-    client: &C,
-    type_: &str,
+fn top_items_url(
+    type_: TopItemType,
     limit: Option<usize>,
     offset: Option<usize>,
     time_range: Option<TimeRange>,
-) -> Result<T> {
-    let url = format!("https://api.spotify.com/v1/me/top/{}", type_);
-    let mut url = Url::parse(&url)?;
+) -> Url {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/me/top").unwrap();
+    // UNWRAP: The URL has a path
+    url.path_segments_mut().unwrap().push(type_.as_str());
 
     set_query_param!(url, limit);
     set_query_param!(url, offset);
@@ -24,6 +26,18 @@ async fn top_recommendations<C: SpotifyClient + ?Sized, T: DeserializeOwned>(
     let time_range = time_range.as_ref().map(|t| t.as_str());
     set_query_param!(url, time_range);
 
+    url
+}
+
+async fn top_items<C: SpotifyClient + ?Sized, T: DeserializeOwned>(
+    client: &C,
+    type_: TopItemType,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    time_range: Option<TimeRange>,
+) -> Result<T> {
+    let url = top_items_url(type_, limit, offset, time_range);
+
     let req = Request::new(Method::Get, url);
     client
         .send_authorized(req)
@@ -31,6 +45,28 @@ async fn top_recommendations<C: SpotifyClient + ?Sized, T: DeserializeOwned>(
         .await
 }
 
+/// Get the current user's top artists, based on calculated affinity.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/personalization/get-users-top-artists-and-tracks/)
+pub async fn top_artists_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    time_range: Option<TimeRange>,
+) -> Result<Page<FullArtist>> {
+    top_items(client, TopItemType::Artists, limit, offset, time_range).await
+}
+
+pub async fn top_artists<C: SpotifyClient + ?Sized>(
+    client: &C,
+    time_range: Option<TimeRange>,
+) -> Result<Page<FullArtist>> {
+    top_artists_with_options(client, None, None, time_range).await
+}
+
+/// Get the current user's top tracks, based on calculated affinity.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/personalization/get-users-top-artists-and-tracks/)
 pub async fn top_tracks_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
     limit: Option<usize>,
@@ -38,7 +74,7 @@ pub async fn top_tracks_with_options<C: SpotifyClient + ?Sized>(
     time_range: Option<TimeRange>,
 ) -> Result<Page<FullTrack>> {
     // It's stunning to me that type inference works here
-    top_recommendations(client, "tracks", limit, offset, time_range).await
+    top_items(client, TopItemType::Tracks, limit, offset, time_range).await
 }
 
 pub async fn top_tracks<C: SpotifyClient + ?Sized>(
@@ -48,19 +84,22 @@ pub async fn top_tracks<C: SpotifyClient + ?Sized>(
     top_tracks_with_options(client, None, None, time_range).await
 }
 
-pub async fn top_albums_with_options<C: SpotifyClient + ?Sized>(
+/// Stream all of the current user's top artists, fetching `limit` artists per request.
+pub fn top_artists_stream<C: SpotifyClient + ?Sized>(
     client: &C,
     limit: Option<usize>,
-    offset: Option<usize>,
     time_range: Option<TimeRange>,
-) -> Result<Page<FullAlbum>> {
-    // It's stunning to me that type inference works here
-    top_recommendations(client, "albums", limit, offset, time_range).await
+) -> Pager<'_, C, FullArtist, Page<FullArtist>> {
+    let url = top_items_url(TopItemType::Artists, limit, None, time_range);
+    Pager::with_items(client, Vec::new(), Some(url))
 }
 
-pub async fn top_albums<C: SpotifyClient + ?Sized>(
+/// Stream all of the current user's top tracks, fetching `limit` tracks per request.
+pub fn top_tracks_stream<C: SpotifyClient + ?Sized>(
     client: &C,
+    limit: Option<usize>,
     time_range: Option<TimeRange>,
-) -> Result<Page<FullAlbum>> {
-    top_albums_with_options(client, None, None, time_range).await
+) -> Pager<'_, C, FullTrack, Page<FullTrack>> {
+    let url = top_items_url(TopItemType::Tracks, limit, None, time_range);
+    Pager::with_items(client, Vec::new(), Some(url))
 }
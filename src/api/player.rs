@@ -1 +1,167 @@
+use crate::api::client::{ClientExt, SpotifyClient};
+use crate::api::pager::{Pageable, Pager};
+use crate::model::page::CursorBasedPage;
+use crate::model::playing::{PlayHistory, Queue};
+use crate::{Error, Result};
+use http_types::{Method, Request, Url};
+use std::borrow::Borrow;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Bound on the play history returned by [`recently_played_with_options`]. Spotify accepts
+/// only one of the two per request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayedCursor {
+    /// Only items played strictly before this time
+    Before(SystemTime),
+    /// Only items played strictly after this time
+    After(SystemTime),
+}
+
+fn unix_millis(time: SystemTime) -> u128 {
+    // Times prior to the epoch can't be represented by Spotify cursors; clamp them
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+fn recently_played_url(limit: Option<usize>, cursor: Option<PlayedCursor>) -> Url {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/me/player/recently-played").unwrap();
+
+    set_query_param!(url, limit);
+
+    match cursor {
+        Some(PlayedCursor::Before(t)) => {
+            let before = Some(unix_millis(t));
+            set_query_param!(url, before);
+        }
+        Some(PlayedCursor::After(t)) => {
+            let after = Some(unix_millis(t));
+            set_query_param!(url, after);
+        }
+        None => {}
+    }
+
+    url
+}
+
+/// Get tracks from the current user's recently played history, up to 50 per request.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/player/get-recently-played/)
+pub async fn recently_played_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+    cursor: Option<PlayedCursor>,
+) -> Result<CursorBasedPage<PlayHistory>> {
+    let url = recently_played_url(limit, cursor);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn recently_played<C: SpotifyClient + ?Sized>(
+    client: &C,
+) -> Result<CursorBasedPage<PlayHistory>> {
+    recently_played_with_options(client, None, None).await
+}
+
+/// Page of play history whose next page is found by following the `before` cursor, rather than
+/// trusting the `next` URL.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(from = "CursorBasedPage<PlayHistory>")]
+pub struct PlayHistoryPage {
+    items: Vec<PlayHistory>,
+    next: Option<String>,
+}
+
+impl From<CursorBasedPage<PlayHistory>> for PlayHistoryPage {
+    fn from(page: CursorBasedPage<PlayHistory>) -> Self {
+        let limit = Some(page.limit);
+        let has_items = !page.items.is_empty();
+        let before = page.cursors.before.filter(|_| has_items);
+
+        let next = before.map(|before| {
+            let mut url = recently_played_url(None, None);
+            set_query_param!(url, limit);
+            let before = Some(before);
+            set_query_param!(url, before);
+            url.into()
+        });
+
+        PlayHistoryPage {
+            items: page.items,
+            next,
+        }
+    }
+}
+
+impl Pageable<PlayHistory> for PlayHistoryPage {
+    fn next_url(&self) -> Option<&str> {
+        self.next.as_deref()
+    }
+
+    fn into_items(self) -> Vec<PlayHistory> {
+        self.items
+    }
+}
+
+/// Stream the current user's play history, most recent first, starting before `before` (or
+/// now) and walking backwards until Spotify has no older items.
+pub fn recently_played_stream<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+    before: Option<SystemTime>,
+) -> Pager<'_, C, PlayHistory, PlayHistoryPage> {
+    let url = recently_played_url(limit, before.map(PlayedCursor::Before));
+    Pager::with_items(client, Vec::new(), Some(url))
+}
+
+/// Get the currently playing item and the items queued after it.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/get-queue)
+pub async fn queue<C: SpotifyClient + ?Sized>(client: &C) -> Result<Queue> {
+    // UNWRAP: Known-valid URL
+    let url = Url::parse("https://api.spotify.com/v1/me/player/queue").unwrap();
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+/// Add a track or episode to the end of the queue on the active device, or on `device_id`.
+pub async fn add_to_queue_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    uri: &str,
+    device_id: Option<&str>,
+) -> Result<()> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/me/player/queue").unwrap();
+
+    let uri = Some(uri);
+    set_query_param!(url, uri);
+    set_query_param!(url, device_id);
+
+    let req = Request::new(Method::Post, url);
+    client.send_authorized(req).expect_success().await
+}
+
+pub async fn add_to_queue<C: SpotifyClient + ?Sized>(client: &C, uri: &str) -> Result<()> {
+    add_to_queue_with_options(client, uri, None).await
+}
+
+/// Add several items to the queue, in order. Spotify only accepts one item per request, so each
+/// item is sent separately and a failure doesn't prevent the remaining items from being queued.
+///
+/// Returns the index in `uris` and the error of each item that couldn't be queued.
+pub async fn add_many_to_queue<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+    client: &C,
+    uris: &[B],
+    device_id: Option<&str>,
+) -> Vec<(usize, Error)> {
+    let mut failed = Vec::new();
+    for (i, uri) in uris.iter().enumerate() {
+        if let Err(e) = add_to_queue_with_options(client, uri.borrow(), device_id).await {
+            failed.push((i, e));
+        }
+    }
+    failed
+}
@@ -1 +1,460 @@
+use crate::api::client::{ClientExt, SpotifyClient};
+use crate::model::cud_result::CUDResult;
+use crate::model::id::{PlaylistId, UserId};
+use crate::model::image::Image;
+use crate::model::page::Page;
+use crate::model::playlist::{FullPlaylist, PlaylistTrack, SimplifiedPlaylist};
+use crate::{Error, Result};
+use http_types::{mime, Body, Method, Request, Url};
+use serde::de::DeserializeOwned;
+use std::borrow::Borrow;
 
+/// Get a playlist, deserialized into `T`.
+///
+/// `fields` limits the response to the fields Spotify is asked for, such as
+/// `name,tracks.items(track(id,name))`. Because the response then only has those fields, it
+/// usually has to be deserialized into a struct of your own rather than a `FullPlaylist`.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/get-playlist)
+pub async fn playlist_with_options<C: SpotifyClient + ?Sized, T: DeserializeOwned>(
+    client: &C,
+    id: &PlaylistId,
+    fields: Option<&str>,
+    market: Option<&str>,
+) -> Result<T> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}", id);
+    let mut url = Url::parse(&url)?;
+
+    set_query_param!(url, fields);
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn playlist<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+) -> Result<FullPlaylist> {
+    playlist_with_options(client, id, None, None).await
+}
+
+/// Get a page of the items in a playlist, deserialized into `T`.
+///
+/// `fields` works as for [`playlist_with_options`], relative to the page, so
+/// `items(track(id,name)),next` is enough to walk a large playlist without downloading full
+/// track objects.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/get-playlists-tracks)
+pub async fn playlist_items_with_options<C: SpotifyClient + ?Sized, T: DeserializeOwned>(
+    client: &C,
+    id: &PlaylistId,
+    fields: Option<&str>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    market: Option<&str>,
+) -> Result<T> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", id);
+    let mut url = Url::parse(&url)?;
+
+    set_query_param!(url, fields);
+    set_query_param!(url, limit);
+    set_query_param!(url, offset);
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn playlist_items<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+) -> Result<Page<PlaylistTrack>> {
+    playlist_items_with_options(client, id, None, None, None, None).await
+}
+
+pub async fn current_user_playlists_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Page<SimplifiedPlaylist>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/me/playlists").unwrap();
+
+    set_query_param!(url, limit);
+    set_query_param!(url, offset);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn current_user_playlists<C: SpotifyClient + ?Sized>(
+    client: &C,
+) -> Result<Page<SimplifiedPlaylist>> {
+    current_user_playlists_with_options(client, None, None).await
+}
+
+pub async fn user_playlists_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    user_id: &UserId,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Page<SimplifiedPlaylist>> {
+    let url = format!("https://api.spotify.com/v1/users/{}/playlists", user_id);
+    let mut url = Url::parse(&url)?;
+
+    set_query_param!(url, limit);
+    set_query_param!(url, offset);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn user_playlists<C: SpotifyClient + ?Sized>(
+    client: &C,
+    user_id: &UserId,
+) -> Result<Page<SimplifiedPlaylist>> {
+    user_playlists_with_options(client, user_id, None, None).await
+}
+
+#[derive(Debug, Serialize)]
+struct PlaylistDetailsBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collaborative: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+pub async fn create_playlist_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    user_id: &UserId,
+    name: &str,
+    public: Option<bool>,
+    collaborative: Option<bool>,
+    description: Option<&str>,
+) -> Result<FullPlaylist> {
+    let url = format!("https://api.spotify.com/v1/users/{}/playlists", user_id);
+    let url = Url::parse(&url)?;
+
+    let body = PlaylistDetailsBody {
+        name: Some(name),
+        public,
+        collaborative,
+        description,
+    };
+
+    let mut req = Request::new(Method::Post, url);
+    req.set_body(Body::from_json(&body)?);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn create_playlist<C: SpotifyClient + ?Sized>(
+    client: &C,
+    user_id: &UserId,
+    name: &str,
+) -> Result<FullPlaylist> {
+    create_playlist_with_options(client, user_id, name, None, None, None).await
+}
+
+/// Change a playlist's name, visibility, collaboration or description. Fields left as `None`
+/// are not changed.
+///
+/// Note that Spotify only allows collaborative playlists to be private.
+pub async fn change_playlist_details<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+    name: Option<&str>,
+    public: Option<bool>,
+    collaborative: Option<bool>,
+    description: Option<&str>,
+) -> Result<()> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}", id);
+    let url = Url::parse(&url)?;
+
+    let body = PlaylistDetailsBody {
+        name,
+        public,
+        collaborative,
+        description,
+    };
+
+    let mut req = Request::new(Method::Put, url);
+    req.set_body(Body::from_json(&body)?);
+    client.send_authorized(req).expect_success().await
+}
+
+#[derive(Debug, Serialize)]
+struct AddItemsBody<'a> {
+    uris: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<usize>,
+}
+
+/// How many items Spotify adds, removes or replaces in one request.
+const MAX_PLAYLIST_ITEMS: usize = 100;
+
+async fn add_items_chunk<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+    client: &C,
+    id: &PlaylistId,
+    uris: &[B],
+    position: Option<usize>,
+) -> Result<CUDResult> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", id);
+    let url = Url::parse(&url)?;
+
+    let body = AddItemsBody {
+        uris: uris.iter().map(|u| u.borrow()).collect(),
+        position,
+    };
+
+    let mut req = Request::new(Method::Post, url);
+    req.set_body(Body::from_json(&body)?);
+    client.send_authorized(req).deserialize_response().await
+}
+
+/// The playlist's current snapshot ID, for changes that have nothing to send. Spotify rejects
+/// an empty list of items rather than leaving the playlist as it is.
+async fn current_snapshot<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+) -> Result<CUDResult> {
+    playlist_with_options(client, id, Some("snapshot_id"), None).await
+}
+
+/// Add tracks or episodes, identified by their Spotify URI, to a playlist. Items are appended
+/// unless a zero-based `position` is provided.
+///
+/// Spotify takes at most 100 items per request, so longer lists are added 100 at a time, one
+/// request after the other. If a request fails, the items before it have already been added.
+/// The snapshot ID returned is that of the last request, or the playlist's current one if there
+/// are no items to add.
+pub async fn add_items_with_options<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+    client: &C,
+    id: &PlaylistId,
+    uris: &[B],
+    position: Option<usize>,
+) -> Result<CUDResult> {
+    let mut chunks = uris.chunks(MAX_PLAYLIST_ITEMS);
+    let first = match chunks.next() {
+        Some(first) => first,
+        None => return current_snapshot(client, id).await,
+    };
+    let mut result = add_items_chunk(client, id, first, position).await?;
+    let mut position = position.map(|p| p + first.len());
+    for chunk in chunks {
+        result = add_items_chunk(client, id, chunk, position).await?;
+        position = position.map(|p| p + chunk.len());
+    }
+    Ok(result)
+}
+
+pub async fn add_items<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+    client: &C,
+    id: &PlaylistId,
+    uris: &[B],
+) -> Result<CUDResult> {
+    add_items_with_options(client, id, uris, None).await
+}
+
+/// Occurrences of an item to remove from a playlist. When `positions` is `None`, every
+/// occurrence of `uri` is removed.
+#[derive(Clone, Debug, Serialize)]
+pub struct ItemPositions<'a> {
+    pub uri: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positions: Option<&'a [u32]>,
+}
+
+#[derive(Debug, Serialize)]
+struct RemoveItemsBody<'a> {
+    tracks: &'a [ItemPositions<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_id: Option<&'a str>,
+}
+
+/// Remove specific occurrences of items from a playlist.
+///
+/// Positions are only meaningful for a specific version of the playlist, so a `snapshot_id`
+/// should be provided whenever positions are; Spotify will reject the change if the playlist
+/// has been modified since. Spotify takes at most 100 items per request.
+pub async fn remove_items_at_positions<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+    items: &[ItemPositions<'_>],
+    snapshot_id: Option<&str>,
+) -> Result<CUDResult> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", id);
+    let url = Url::parse(&url)?;
+
+    let body = RemoveItemsBody {
+        tracks: items,
+        snapshot_id,
+    };
+
+    let mut req = Request::new(Method::Delete, url);
+    req.set_body(Body::from_json(&body)?);
+    client.send_authorized(req).deserialize_response().await
+}
+
+/// Remove all occurrences of the items identified by `uris` from a playlist.
+///
+/// Longer lists than Spotify takes at once are removed 100 at a time, one request after the
+/// other, each made against the snapshot the previous one returned. If a request fails, the
+/// items before it have already been removed. With no items, nothing is removed and the
+/// playlist's current snapshot ID is returned.
+pub async fn remove_items<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+    client: &C,
+    id: &PlaylistId,
+    uris: &[B],
+    snapshot_id: Option<&str>,
+) -> Result<CUDResult> {
+    let items = uris
+        .iter()
+        .map(|u| ItemPositions {
+            uri: u.borrow(),
+            positions: None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut chunks = items.chunks(MAX_PLAYLIST_ITEMS);
+    let first = match chunks.next() {
+        Some(first) => first,
+        None => return current_snapshot(client, id).await,
+    };
+    let mut result = remove_items_at_positions(client, id, first, snapshot_id).await?;
+    for chunk in chunks {
+        let snapshot_id = Some(result.snapshot_id.as_str());
+        result = remove_items_at_positions(client, id, chunk, snapshot_id).await?;
+    }
+    Ok(result)
+}
+
+#[derive(Debug, Serialize)]
+struct ReorderItemsBody<'a> {
+    range_start: usize,
+    insert_before: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_id: Option<&'a str>,
+}
+
+/// Move `range_length` items (default 1) starting at `range_start` so they are placed before
+/// the item currently at `insert_before`.
+pub async fn reorder_items_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+    range_start: usize,
+    insert_before: usize,
+    range_length: Option<usize>,
+    snapshot_id: Option<&str>,
+) -> Result<CUDResult> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", id);
+    let url = Url::parse(&url)?;
+
+    let body = ReorderItemsBody {
+        range_start,
+        insert_before,
+        range_length,
+        snapshot_id,
+    };
+
+    let mut req = Request::new(Method::Put, url);
+    req.set_body(Body::from_json(&body)?);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn reorder_items<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+    range_start: usize,
+    insert_before: usize,
+) -> Result<CUDResult> {
+    reorder_items_with_options(client, id, range_start, insert_before, None, None).await
+}
+
+#[derive(Debug, Serialize)]
+struct ReplaceItemsBody<'a> {
+    uris: Vec<&'a str>,
+}
+
+/// Replace every item in a playlist with the items identified by `uris`. An empty slice clears
+/// the playlist.
+///
+/// Spotify replaces at most 100 items per request, so the rest of a longer list is then added
+/// 100 at a time as by [`add_items`]. If one of those requests fails, the playlist is left with
+/// only the items sent before it.
+pub async fn replace_items<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+    client: &C,
+    id: &PlaylistId,
+    uris: &[B],
+) -> Result<CUDResult> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", id);
+    let url = Url::parse(&url)?;
+
+    let mut chunks = uris.chunks(MAX_PLAYLIST_ITEMS);
+    let body = ReplaceItemsBody {
+        uris: chunks
+            .next()
+            .unwrap_or(&[])
+            .iter()
+            .map(|u| u.borrow())
+            .collect(),
+    };
+
+    let mut req = Request::new(Method::Put, url);
+    req.set_body(Body::from_json(&body)?);
+    let mut result = client.send_authorized(req).deserialize_response().await?;
+    for chunk in chunks {
+        result = add_items_chunk(client, id, chunk, None).await?;
+    }
+    Ok(result)
+}
+
+pub async fn playlist_cover_image<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+) -> Result<Vec<Image>> {
+    let url = format!("https://api.spotify.com/v1/playlists/{}/images", id);
+    let url = Url::parse(&url)?;
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+const JPEG_MAGIC: [u8; 3] = [0xFF, 0xD8, 0xFF];
+const MAX_COVER_IMAGE_SIZE: usize = 256 * 1024;
+
+/// Replace the cover image of a playlist with a custom JPEG. Requires the `ugc-image-upload`
+/// scope in addition to a playlist modification scope.
+///
+/// Spotify limits the base64-encoded image to 256 KB; images that aren't JPEGs or that are too
+/// large are rejected without making a request.
+pub async fn upload_playlist_cover_image<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &PlaylistId,
+    jpeg: &[u8],
+) -> Result<()> {
+    if !jpeg.starts_with(&JPEG_MAGIC) {
+        return Err(Error::NotJpeg);
+    }
+
+    let encoded = base64::encode(jpeg);
+    if encoded.len() > MAX_COVER_IMAGE_SIZE {
+        return Err(Error::ImageTooLarge(encoded.len()));
+    }
+
+    let url = format!("https://api.spotify.com/v1/playlists/{}/images", id);
+    let url = Url::parse(&url)?;
+
+    let mut body = Body::from_string(encoded);
+    body.set_mime(mime::JPEG);
+
+    let mut req = Request::new(Method::Put, url);
+    req.set_body(body);
+    client.send_authorized(req).expect_success().await
+}
@@ -0,0 +1,44 @@
+use crate::api::client::{check_status, SpotifyClient};
+use crate::Result;
+use http_types::{Body, Method, Request, Url};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+const API_BASE: &str = "https://api.spotify.com/v1/";
+
+/// Call an endpoint this crate doesn't wrap yet. `path` is relative to the API base, like
+/// `me/player/devices`, and `query` pairs are appended to it as they are.
+///
+/// The request is authorized and its errors are reported the same way as for the wrapped
+/// endpoints. Endpoints that answer without a body can be read into `()` or an `Option`.
+///
+/// ```rust,ignore
+/// let devices: Devices = raw::request(&client, Method::Get, "me/player/devices", &[], None).await?;
+/// ```
+pub async fn request<C: SpotifyClient + ?Sized, T: DeserializeOwned>(
+    client: &C,
+    method: Method,
+    path: &str,
+    query: &[(&str, &str)],
+    body: Option<&Value>,
+) -> Result<T> {
+    let url = format!("{}{}", API_BASE, path.trim_start_matches('/'));
+    let mut url = Url::parse(&url)?;
+
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+
+    let mut req = Request::new(method, url);
+    if let Some(body) = body {
+        req.set_body(Body::from_json(body)?);
+    }
+
+    let mut resp = check_status(client.send_authorized(req).await?).await?;
+    let body = resp.body_string().await?;
+    let body = match body.trim() {
+        "" => "null",
+        body => body,
+    };
+    serde_json::from_str(body).map_err(|e| e.into())
+}
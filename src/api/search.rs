@@ -1 +1,144 @@
+use crate::api::client::{ClientExt, SpotifyClient};
+use crate::model::search::SearchResults;
+use crate::model::senum::{IncludeExternal, SearchType};
+use crate::Result;
+use http_types::{Method, Request, Url};
+use std::fmt;
+use std::ops::RangeInclusive;
 
+/// Builder for the `q` parameter of a search, handling field filters and quoting.
+///
+/// ```rust
+/// use spotify_api::search::SearchQuery;
+///
+/// let q = SearchQuery::new()
+///     .term("bohemian rhapsody")
+///     .artist("Queen")
+///     .years(1970..=1979);
+/// assert_eq!(q.to_string(), r#""bohemian rhapsody" artist:Queen year:1970-1979"#);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    parts: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(mut self, filter: Option<&str>, value: &str) -> Self {
+        // Spotify has no escape sequence for quotes, so the only safe option is to drop them
+        let value = value.replace('"', "");
+        let value = value.trim();
+        if value.is_empty() {
+            return self;
+        }
+
+        let value = if value.contains(char::is_whitespace) {
+            format!("\"{}\"", value)
+        } else {
+            value.to_owned()
+        };
+
+        match filter {
+            Some(f) => self.parts.push(format!("{}:{}", f, value)),
+            None => self.parts.push(value),
+        }
+        self
+    }
+
+    /// Free-text keywords, matched against names and descriptions.
+    pub fn term(self, term: &str) -> Self {
+        self.push(None, term)
+    }
+
+    pub fn artist(self, artist: &str) -> Self {
+        self.push(Some("artist"), artist)
+    }
+
+    pub fn album(self, album: &str) -> Self {
+        self.push(Some("album"), album)
+    }
+
+    pub fn track(self, track: &str) -> Self {
+        self.push(Some("track"), track)
+    }
+
+    pub fn genre(self, genre: &str) -> Self {
+        self.push(Some("genre"), genre)
+    }
+
+    pub fn isrc(self, isrc: &str) -> Self {
+        self.push(Some("isrc"), isrc)
+    }
+
+    pub fn upc(self, upc: &str) -> Self {
+        self.push(Some("upc"), upc)
+    }
+
+    pub fn year(self, year: u16) -> Self {
+        self.push(Some("year"), &year.to_string())
+    }
+
+    pub fn years(self, years: RangeInclusive<u16>) -> Self {
+        let range = format!("{}-{}", years.start(), years.end());
+        self.push(Some("year"), &range)
+    }
+
+    /// Only albums released in the past two weeks. Applies to album searches.
+    pub fn tag_new(self) -> Self {
+        self.push(Some("tag"), "new")
+    }
+
+    /// Only albums with the lowest 10% popularity. Applies to album searches.
+    pub fn tag_hipster(self) -> Self {
+        self.push(Some("tag"), "hipster")
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.parts.join(" "))
+    }
+}
+
+/// Get catalog information about items matching a keyword string. Only the requested `types`
+/// are populated in the result.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/search/search/)
+pub async fn search_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    query: &SearchQuery,
+    types: &[SearchType],
+    market: Option<&str>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_external: Option<IncludeExternal>,
+) -> Result<SearchResults> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/search").unwrap();
+
+    let types = types.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+    url.query_pairs_mut()
+        .append_pair("q", &query.to_string())
+        .append_pair("type", &types.join(","));
+
+    set_query_param!(url, market);
+    set_query_param!(url, limit);
+    set_query_param!(url, offset);
+
+    let include_external = include_external.as_ref().map(|i| i.as_str());
+    set_query_param!(url, include_external);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn search<C: SpotifyClient + ?Sized>(
+    client: &C,
+    query: &SearchQuery,
+    types: &[SearchType],
+) -> Result<SearchResults> {
+    search_with_options(client, query, types, None, None, None, None).await
+}
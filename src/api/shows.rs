@@ -1,14 +1,15 @@
+use crate::api::chunks::chunked;
 use crate::api::{ClientExt, SpotifyClient};
+use crate::model::id::ShowId;
 use crate::model::page::Page;
-use crate::model::show::{FullShow, SeversalSimplifiedShows, SimplifiedEpisode};
+use crate::model::show::{FullShow, SeversalSimplifiedShows, SimplifiedEpisode, SimplifiedShow};
 use crate::Result;
 
 use http_types::{Method, Request, Url};
-use std::borrow::Borrow;
 
 pub async fn show_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &ShowId,
     market: Option<&str>,
 ) -> Result<FullShow> {
     let url = format!("https://api.spotify.com/v1/shows/{}", id);
@@ -20,15 +21,17 @@ pub async fn show_with_options<C: SpotifyClient + ?Sized>(
     client.send_authorized(req).deserialize_response().await
 }
 
-pub async fn show<C: SpotifyClient + ?Sized>(client: &C, id: &str) -> Result<FullShow> {
+pub async fn show<C: SpotifyClient + ?Sized>(client: &C, id: &ShowId) -> Result<FullShow> {
     show_with_options(client, id, None).await
 }
 
-pub async fn shows_with_options<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+const MAX_SHOW_IDS: usize = 50;
+
+async fn shows_chunk<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[ShowId],
     market: Option<&str>,
-) -> Result<SeversalSimplifiedShows> {
+) -> Result<Vec<Option<SimplifiedShow>>> {
     // UNWRAP: Known-valid URL
     let mut url = Url::parse("https://api.spotify.com/v1/shows").unwrap();
 
@@ -36,19 +39,31 @@ pub async fn shows_with_options<C: SpotifyClient + ?Sized, B: Borrow<str>>(
     set_query_param_joined!(url, ids);
 
     let req = Request::new(Method::Get, url);
-    client.send_authorized(req).deserialize_response().await
+    let response: SeversalSimplifiedShows =
+        client.send_authorized(req).deserialize_response().await?;
+    Ok(response.shows)
+}
+
+/// Get catalog information for any number of shows, requested 50 at a time.
+pub async fn shows_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[ShowId],
+    market: Option<&str>,
+) -> Result<SeversalSimplifiedShows> {
+    let shows = chunked(ids, MAX_SHOW_IDS, |ids| shows_chunk(client, ids, market)).await?;
+    Ok(SeversalSimplifiedShows { shows })
 }
 
-pub async fn shows<C: SpotifyClient + ?Sized, B: Borrow<str>>(
+pub async fn shows<C: SpotifyClient + ?Sized>(
     client: &C,
-    ids: &[B],
+    ids: &[ShowId],
 ) -> Result<SeversalSimplifiedShows> {
     shows_with_options(client, ids, None).await
 }
 
 pub async fn show_episodes_with_options<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &ShowId,
     limit: Option<usize>,
     offset: Option<usize>,
     market: Option<&str>,
@@ -66,7 +81,7 @@ pub async fn show_episodes_with_options<C: SpotifyClient + ?Sized>(
 
 pub async fn show_episodes<C: SpotifyClient + ?Sized>(
     client: &C,
-    id: &str,
+    id: &ShowId,
 ) -> Result<Page<SimplifiedEpisode>> {
     show_episodes_with_options(client, id, None, None, None).await
 }
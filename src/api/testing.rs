@@ -0,0 +1,408 @@
+//! Recording responses from Spotify to cassette files, and replaying them offline in tests.
+//!
+//! Record once against the real API with credentials:
+//!
+//! ```rust,ignore
+//! let client = CassetteClient::record(spotify_client, "tests/cassettes/library.json".into());
+//! library::library_save_tracks(&client, &ids).await?;
+//! client.save()?;
+//! ```
+//!
+//! and from then on replay without network access or a token:
+//!
+//! ```rust,ignore
+//! let client = CassetteClient::replay("tests/cassettes/library.json".into())?;
+//! library::library_save_tracks(&client, &ids).await?;
+//! ```
+use futures::future::ready;
+use http_client::HttpClient;
+use http_types::headers::CONTENT_TYPE;
+use http_types::{Body, Method, Response, StatusCode, Url};
+use serde_json::Value;
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+use crate::api::client::HttpClientFuture;
+use crate::api::SpotifyClient;
+
+/// Query parameters and JSON fields that are replaced with [`REDACTED`] before anything is
+/// written to a cassette.
+const SECRETS: &[&str] = &["access_token", "refresh_token", "client_secret"];
+
+pub const REDACTED: &str = "REDACTED";
+
+/// Response headers that are only noise in a cassette, or could identify the session.
+const SKIPPED_HEADERS: &[&str] = &["date", "set-cookie", "x-request-id", "via", "alt-svc"];
+
+#[derive(Debug, Error)]
+pub enum CassetteError {
+    #[error("")]
+    Io(#[from] io::Error),
+
+    #[error("")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A request as it is matched against during replay. Headers aren't kept, so the
+/// `Authorization` header never reaches a cassette.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// The interactions recorded in one cassette file, in the order they happened.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn load(path: &PathBuf) -> Result<Self, CassetteError> {
+        // Note: to avoid expressing a preference for a specific runtime, cassettes are read and
+        // written with synchronous I/O.
+        let bytes = fs::read(path)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<(), CassetteError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut bytes = serde_json::to_vec_pretty(self)?;
+        bytes.push(b'\n');
+        Ok(fs::write(path, bytes)?)
+    }
+}
+
+/// Returns whether anything was redacted.
+fn scrub_json(value: &mut Value) -> bool {
+    match value {
+        Value::Object(map) => {
+            let mut scrubbed = false;
+            for (key, value) in map.iter_mut() {
+                if SECRETS.contains(&key.as_str()) && !value.is_null() {
+                    *value = Value::String(REDACTED.to_owned());
+                    scrubbed = true;
+                } else {
+                    scrubbed |= scrub_json(value);
+                }
+            }
+            scrubbed
+        }
+        Value::Array(values) => values.iter_mut().fold(false, |s, v| scrub_json(v) | s),
+        _ => false,
+    }
+}
+
+// Bodies without secrets are kept exactly as they were
+fn scrub_body(body: String) -> String {
+    match serde_json::from_str::<Value>(&body) {
+        Ok(mut value) => match scrub_json(&mut value) {
+            true => value.to_string(),
+            false => body,
+        },
+        Err(_) => body,
+    }
+}
+
+fn scrub_url(url: &Url) -> String {
+    if url.query().is_none() {
+        return url.to_string();
+    }
+
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| match SECRETS.contains(&k.as_ref()) {
+            true => (k.into_owned(), REDACTED.to_owned()),
+            false => (k.into_owned(), v.into_owned()),
+        })
+        .collect();
+    let mut url = url.clone();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url.to_string()
+}
+
+async fn record_request(
+    req: &mut http_client::Request,
+) -> Result<RecordedRequest, http_client::Error> {
+    // Taking and setting the body both give the request a `Content-Type` if it has none, so
+    // note whether it had one to begin with
+    let had_content_type = req.header(CONTENT_TYPE).is_some();
+    let body = req.take_body();
+    let mime = body.mime().clone();
+    let body = body.into_string().await?;
+    let recorded = RecordedRequest {
+        method: req.method().to_string(),
+        url: scrub_url(req.url()),
+        body: Some(body.clone()).filter(|b| !b.is_empty()).map(scrub_body),
+    };
+    if body.is_empty() {
+        req.set_body(Body::empty());
+    } else {
+        let mut body = Body::from_string(body);
+        body.set_mime(mime);
+        req.set_body(body);
+    }
+    if !had_content_type {
+        req.remove_header(CONTENT_TYPE);
+    }
+    Ok(recorded)
+}
+
+async fn record_response(mut resp: Response) -> Result<RecordedResponse, http_client::Error> {
+    let headers = resp
+        .iter()
+        .filter(|(name, _)| !SKIPPED_HEADERS.contains(&name.as_str()))
+        .flat_map(|(name, values)| {
+            values
+                .iter()
+                .map(move |v| (name.as_str().to_owned(), v.as_str().to_owned()))
+        })
+        .collect();
+    Ok(RecordedResponse {
+        status: resp.status().into(),
+        headers,
+        body: resp.body_string().await?,
+    })
+}
+
+impl RecordedRequest {
+    /// Requests match when they have the same method, path and query parameters in any order,
+    /// and equal bodies, compared as JSON where they are JSON.
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        fn query(url: &str) -> Option<(Url, Vec<(String, String)>)> {
+            let url = Url::parse(url).ok()?;
+            let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+            pairs.sort();
+            Some((url, pairs))
+        }
+        fn body(body: &Option<String>) -> Option<Value> {
+            body.as_ref()
+                .map(|b| serde_json::from_str(b).unwrap_or_else(|_| Value::String(b.clone())))
+        }
+
+        let urls_match = match (query(&self.url), query(&other.url)) {
+            (Some((a, a_pairs)), Some((b, b_pairs))) => {
+                a.origin() == b.origin() && a.path() == b.path() && a_pairs == b_pairs
+            }
+            _ => self.url == other.url,
+        };
+        self.method.eq_ignore_ascii_case(&other.method)
+            && urls_match
+            && body(&self.body) == body(&other.body)
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> Response {
+        let status = StatusCode::try_from(self.status).unwrap_or(StatusCode::InternalServerError);
+        let mut resp = Response::new(status);
+        for (name, value) in &self.headers {
+            resp.append_header(name.as_str(), value.as_str());
+        }
+        resp.set_body(self.body.as_str());
+        resp
+    }
+}
+
+struct Tape {
+    cassette: Cassette,
+    played: Vec<bool>,
+}
+
+impl Tape {
+    /// The first interaction matching the request that hasn't been played yet. Once they all
+    /// have, GET requests are answered with the last matching one again, since asking twice for
+    /// the same thing doesn't change it.
+    fn play(&mut self, req: &RecordedRequest) -> Option<Response> {
+        let mut matching = self
+            .cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.request.matches(req));
+        let index = match matching.clone().find(|(n, _)| !self.played[*n]) {
+            Some((n, _)) => n,
+            None if req.method == Method::Get.to_string() => matching.next_back()?.0,
+            None => return None,
+        };
+        self.played[index] = true;
+        Some(self.cassette.interactions[index].response.to_response())
+    }
+}
+
+enum Mode<C> {
+    Record {
+        client: Arc<C>,
+        path: PathBuf,
+        cassette: Arc<Mutex<Cassette>>,
+    },
+    Replay(Arc<Mutex<Tape>>),
+}
+
+/// Client that either records the authorized requests it sends to a cassette, or answers them
+/// from one without touching the network.
+///
+/// Only authorized requests are recorded; unauthorized ones, like the token exchange, are passed
+/// through when recording and fail when replaying. Tokens and secrets are scrubbed from URLs and
+/// JSON bodies before they are written, and request headers aren't recorded at all.
+///
+/// When replaying, every request has to match a recorded one, or it fails with a `404`-status
+/// `http_client::Error` naming the request.
+pub struct CassetteClient<C> {
+    mode: Mode<C>,
+}
+
+/// Stand-in for the client a [`CassetteClient`] would record from, when it only replays.
+#[derive(Debug)]
+pub struct Offline;
+
+impl HttpClient for Offline {
+    fn send(&self, req: http_client::Request) -> HttpClientFuture {
+        Box::pin(ready(Err(http_client::Error::from_str(
+            StatusCode::NotFound,
+            format!(
+                "no network access while replaying: {} {}",
+                req.method(),
+                req.url()
+            ),
+        ))))
+    }
+}
+
+impl SpotifyClient for Offline {
+    fn send_authorized(&self, req: http_client::Request) -> HttpClientFuture {
+        self.send(req)
+    }
+}
+
+impl<C: SpotifyClient> CassetteClient<C> {
+    /// Send requests through `client`, keeping every interaction until [`save`](Self::save)
+    /// writes them to `path`.
+    pub fn record(client: C, path: PathBuf) -> Self {
+        CassetteClient {
+            mode: Mode::Record {
+                client: Arc::new(client),
+                path,
+                cassette: Arc::default(),
+            },
+        }
+    }
+
+    /// The interactions recorded so far, or the ones being replayed.
+    pub fn cassette(&self) -> Cassette {
+        // UNWRAP: The lock is never held across a panic
+        match &self.mode {
+            Mode::Record { cassette, .. } => cassette.lock().unwrap().clone(),
+            Mode::Replay(tape) => tape.lock().unwrap().cassette.clone(),
+        }
+    }
+
+    /// Write the interactions recorded so far. Does nothing when replaying.
+    pub fn save(&self) -> Result<(), CassetteError> {
+        match &self.mode {
+            Mode::Record { path, cassette, .. } => {
+                // UNWRAP: The lock is never held across a panic
+                cassette.lock().unwrap().save(path)
+            }
+            Mode::Replay(_) => Ok(()),
+        }
+    }
+}
+
+impl CassetteClient<Offline> {
+    pub fn replay(path: PathBuf) -> Result<Self, CassetteError> {
+        Ok(Self::from_cassette(Cassette::load(&path)?))
+    }
+
+    pub fn from_cassette(cassette: Cassette) -> Self {
+        let played = vec![false; cassette.interactions.len()];
+        CassetteClient {
+            mode: Mode::Replay(Arc::new(Mutex::new(Tape { cassette, played }))),
+        }
+    }
+}
+
+impl<C: SpotifyClient> std::fmt::Debug for CassetteClient<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.mode {
+            Mode::Record { client, path, .. } => f
+                .debug_struct("CassetteClient")
+                .field("recording", path)
+                .field("client", client)
+                .finish(),
+            Mode::Replay(_) => f.debug_struct("CassetteClient").finish(),
+        }
+    }
+}
+
+impl<C: SpotifyClient> HttpClient for CassetteClient<C> {
+    fn send(&self, req: http_client::Request) -> HttpClientFuture {
+        match &self.mode {
+            Mode::Record { client, .. } => client.send(req),
+            Mode::Replay(_) => Offline.send(req),
+        }
+    }
+}
+
+impl<C: SpotifyClient> SpotifyClient for CassetteClient<C> {
+    fn send_authorized(&self, mut req: http_client::Request) -> HttpClientFuture {
+        match &self.mode {
+            Mode::Record {
+                client, cassette, ..
+            } => {
+                let client = Arc::clone(client);
+                let cassette = Arc::clone(cassette);
+                Box::pin(async move {
+                    let request = record_request(&mut req).await?;
+                    let mut response = record_response(client.send_authorized(req).await?).await?;
+                    // The caller still gets the response as Spotify sent it
+                    let resp = response.to_response();
+                    response.body = scrub_body(response.body);
+                    // UNWRAP: The lock is never held across a panic
+                    let mut cassette = cassette.lock().unwrap();
+                    cassette
+                        .interactions
+                        .push(Interaction { request, response });
+                    Ok(resp)
+                })
+            }
+            Mode::Replay(tape) => {
+                let tape = Arc::clone(tape);
+                Box::pin(async move {
+                    let request = record_request(&mut req).await?;
+                    // UNWRAP: The lock is never held across a panic
+                    let resp = tape.lock().unwrap().play(&request);
+                    resp.ok_or_else(|| {
+                        http_client::Error::from_str(
+                            StatusCode::NotFound,
+                            format!(
+                                "no recorded interaction matches {} {}",
+                                request.method, request.url
+                            ),
+                        )
+                    })
+                })
+            }
+        }
+    }
+}
@@ -1 +1,107 @@
+use crate::api::chunks::chunked;
+use crate::api::client::{ClientExt, SpotifyClient};
+use crate::model::audio::{AudioAnalysis, AudioFeatures, AudioFeaturesPayload};
+use crate::model::id::TrackId;
+use crate::model::track::{FullTrack, FullTracks};
+use crate::Result;
+use http_types::{Method, Request, Url};
 
+pub async fn track_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &TrackId,
+    market: Option<&str>,
+) -> Result<FullTrack> {
+    let url = format!("https://api.spotify.com/v1/tracks/{}", id);
+    let mut url = Url::parse(&url)?;
+
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+pub async fn track<C: SpotifyClient + ?Sized>(client: &C, id: &TrackId) -> Result<FullTrack> {
+    track_with_options(client, id, None).await
+}
+
+const MAX_TRACK_IDS: usize = 50;
+
+async fn tracks_chunk<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[TrackId],
+    market: Option<&str>,
+) -> Result<Vec<Option<FullTrack>>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/tracks").unwrap();
+
+    set_query_param_joined!(url, ids);
+    set_query_param!(url, market);
+
+    let req = Request::new(Method::Get, url);
+    let response: FullTracks = client.send_authorized(req).deserialize_response().await?;
+    Ok(response.tracks)
+}
+
+/// Get catalog information for any number of tracks, requested 50 at a time.
+pub async fn tracks_with_options<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[TrackId],
+    market: Option<&str>,
+) -> Result<FullTracks> {
+    let tracks = chunked(ids, MAX_TRACK_IDS, |ids| tracks_chunk(client, ids, market)).await?;
+    Ok(FullTracks { tracks })
+}
+
+pub async fn tracks<C: SpotifyClient + ?Sized>(client: &C, ids: &[TrackId]) -> Result<FullTracks> {
+    tracks_with_options(client, ids, None).await
+}
+
+pub async fn audio_features<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &TrackId,
+) -> Result<AudioFeatures> {
+    let url = format!("https://api.spotify.com/v1/audio-features/{}", id);
+    let url = Url::parse(&url)?;
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+const MAX_AUDIO_FEATURES_IDS: usize = 100;
+
+async fn several_audio_features_chunk<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[TrackId],
+) -> Result<Vec<Option<AudioFeatures>>> {
+    // UNWRAP: Known-valid URL
+    let mut url = Url::parse("https://api.spotify.com/v1/audio-features").unwrap();
+
+    set_query_param_joined!(url, ids);
+
+    let req = Request::new(Method::Get, url);
+    let response: AudioFeaturesPayload = client.send_authorized(req).deserialize_response().await?;
+    Ok(response.audio_features)
+}
+
+/// Get audio features for any number of tracks, requested 100 at a time.
+pub async fn several_audio_features<C: SpotifyClient + ?Sized>(
+    client: &C,
+    ids: &[TrackId],
+) -> Result<AudioFeaturesPayload> {
+    let audio_features = chunked(ids, MAX_AUDIO_FEATURES_IDS, |ids| {
+        several_audio_features_chunk(client, ids)
+    })
+    .await?;
+    Ok(AudioFeaturesPayload { audio_features })
+}
+
+pub async fn audio_analysis<C: SpotifyClient + ?Sized>(
+    client: &C,
+    id: &TrackId,
+) -> Result<AudioAnalysis> {
+    let url = format!("https://api.spotify.com/v1/audio-analysis/{}", id);
+    let url = Url::parse(&url)?;
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
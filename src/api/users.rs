@@ -1 +1,29 @@
+use crate::api::client::{ClientExt, SpotifyClient};
+use crate::model::id::UserId;
+use crate::model::user::{PrivateUser, PublicUser};
+use crate::Result;
+use http_types::{Method, Request, Url};
 
+pub use crate::api::playlists::{user_playlists, user_playlists_with_options};
+
+/// Get detailed profile information about the current user.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/users-profile/get-current-users-profile/)
+pub async fn current_user<C: SpotifyClient + ?Sized>(client: &C) -> Result<PrivateUser> {
+    // UNWRAP: Known-valid URL
+    let url = Url::parse("https://api.spotify.com/v1/me").unwrap();
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
+
+/// Get public profile information about a Spotify user.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/users-profile/get-users-profile/)
+pub async fn user<C: SpotifyClient + ?Sized>(client: &C, id: &UserId) -> Result<PublicUser> {
+    let url = format!("https://api.spotify.com/v1/users/{}", id);
+    let url = Url::parse(&url)?;
+
+    let req = Request::new(Method::Get, url);
+    client.send_authorized(req).deserialize_response().await
+}
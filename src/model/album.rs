@@ -1,10 +1,12 @@
 //! All objects related to album defined by Spotify API
 
-use std::collections::HashMap;
-
 use super::artist::SimplifiedArtist;
+use super::common::Extra;
+use super::common::{Copyright, ExternalIds, ExternalUrls};
+use super::id::{AlbumId, Uri};
 use super::image::Image;
 use super::page::Page;
+use super::release_date::ReleaseDate;
 use super::senum::{AlbumType, Type};
 use super::timestamp::Timestamp;
 use super::track::SimplifiedTrack;
@@ -14,29 +16,38 @@ use super::track::SimplifiedTrack;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimplifiedAlbum {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub album_group: Option<String>,
-    pub album_type: Option<String>,
+    pub album_group: Option<AlbumType>,
+    pub album_type: Option<AlbumType>,
     pub artists: Vec<SimplifiedArtist>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub available_markets: Vec<String>,
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     pub href: Option<String>,
-    pub id: Option<String>,
+    pub id: Option<AlbumId>,
     pub images: Vec<Image>,
-    pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub release_date: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub release_date_precision: Option<String>,
+    pub is_playable: Option<bool>,
+    pub name: String,
+    #[serde(
+        flatten,
+        with = "super::release_date::optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub release_date: Option<ReleaseDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub restrictions: Option<Restrictions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_tracks: Option<u32>,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: Option<String>,
+    pub uri: Option<Uri<AlbumId>>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// Restrictions object
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Restrictions {
     pub reason: String,
 }
@@ -48,31 +59,42 @@ pub struct FullAlbum {
     pub artists: Vec<SimplifiedArtist>,
     pub album_type: AlbumType,
     pub available_markets: Vec<String>,
-    pub copyrights: Vec<HashMap<String, String>>,
-    pub external_ids: HashMap<String, String>,
-    pub external_urls: HashMap<String, String>,
+    pub copyrights: Vec<Copyright>,
+    pub external_ids: ExternalIds,
+    pub external_urls: ExternalUrls,
     pub genres: Vec<String>,
     pub href: String,
-    pub id: String,
+    pub id: AlbumId,
     pub images: Vec<Image>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_playable: Option<bool>,
+    pub label: Option<String>,
     pub name: String,
     pub popularity: u32,
-    pub release_date: String,
-    pub release_date_precision: String,
+    #[serde(flatten)]
+    pub release_date: ReleaseDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
+    pub total_tracks: u32,
     pub tracks: Page<SimplifiedTrack>,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: String,
+    pub uri: Uri<AlbumId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// Full Albums
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullAlbums {
-    pub albums: Vec<FullAlbum>,
+    /// `None` in the position of any ID that wasn't found
+    pub albums: Vec<Option<FullAlbum>>,
 }
 
 ///[link to get list new releases](https://developer.spotify.com/web-api/get-list-new-releases/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct NewReleases {
     // Note that despite Spotify's claims to include a "message" attribute, it doesn't actually
     // exist in the API calls currently. The attribute is present here just in case.
@@ -83,6 +105,7 @@ pub struct NewReleases {
 ///[link to save album object](https://developer.spotify.com/web-api/object-model/#save-album-object)
 /// Saved Album object
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SavedAlbum {
     pub added_at: Timestamp,
     pub album: FullAlbum,
@@ -1,51 +1,63 @@
 //! All objects related to artist defined by Spotify API
 
+use super::common::Extra;
+use super::common::{ExternalUrls, Followers};
+use super::id::{ArtistId, Uri};
 use super::image::Image;
 use super::page::CursorBasedPage;
 use super::senum::Type;
-use serde_json::Value;
-use std::collections::HashMap;
 ///[artist object simplified](https://developer.spotify.com/web-api/object-model/#artist-object-simplified)
 /// Simplified Artist Object
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimplifiedArtist {
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     pub href: Option<String>,
-    pub id: Option<String>,
+    pub id: Option<ArtistId>,
     pub name: String,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: Option<String>,
+    pub uri: Option<Uri<ArtistId>>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 ///[artist object full](https://developer.spotify.com/web-api/object-model/#artist-object-full)
 /// Full Artist Object
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FullArtist {
-    pub external_urls: HashMap<String, String>,
-    pub followers: HashMap<String, Option<Value>>,
+    pub external_urls: ExternalUrls,
+    pub followers: Followers,
     pub genres: Vec<String>,
     pub href: String,
-    pub id: String,
+    pub id: ArtistId,
     pub images: Vec<Image>,
     pub name: String,
     pub popularity: u32,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: String,
+    pub uri: Uri<ArtistId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// Full artist vector
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullArtists {
-    // TODO: Think this should be Option<FullArtist>?
-    // As the docs state - if an object isn't found, null values will be stored in the relevant
-    // entry position.
+    /// An artist that isn't found is `None` in the position of its ID
+    pub artists: Vec<Option<FullArtist>>,
+}
+
+/// Artists related to an artist
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct RelatedArtists {
     pub artists: Vec<FullArtist>,
 }
 
 /// Full Artists vector wrapped by cursor-based-page object
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FollowedArtists {
     pub artists: CursorBasedPage<FullArtist>,
 }
@@ -1,4 +1,7 @@
 //! All objects related to artist defined by Spotify API
+use super::common::Extra;
+use super::id::{TrackId, Uri};
+use super::senum::Type;
 
 ///[audio feature object](https://developer.spotify.com/web-api/object-model/#audio-features-object)
 /// Audio Feature object
@@ -9,7 +12,7 @@ pub struct AudioFeatures {
     pub danceability: f32,
     pub duration_ms: u32,
     pub energy: f32,
-    pub id: String,
+    pub id: TrackId,
     pub instrumentalness: f32,
     pub key: i32,
     pub liveness: f32,
@@ -20,20 +23,25 @@ pub struct AudioFeatures {
     pub time_signature: i32,
     pub track_href: String,
     #[serde(rename = "type")]
-    pub type_: String,
-    pub uri: String,
+    pub type_: Type,
+    pub uri: Uri<TrackId>,
     pub valence: f32,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// Audio Feature Vector
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioFeaturesPayload {
-    pub audio_features: Vec<AudioFeatures>,
+    /// `None` for tracks that are unknown or have no audio features
+    pub audio_features: Vec<Option<AudioFeatures>>,
 }
 
 /// Audio Analysis Object
 ///[audio analysis](https://developer.spotify.com/web-api/get-audio-analysis/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysis {
     pub bars: Vec<AudioAnalysisMeasure>,
     pub beats: Vec<AudioAnalysisMeasure>,
@@ -46,6 +54,7 @@ pub struct AudioAnalysis {
 
 ///[audio analysis](https://developer.spotify.com/web-api/get-audio-analysis/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysisMeasure {
     pub start: f32,
     pub duration: f32,
@@ -54,6 +63,7 @@ pub struct AudioAnalysisMeasure {
 
 ///[audio analysis](https://developer.spotify.com/web-api/get-audio-analysis/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysisSection {
     pub start: f32,
     pub duration: f32,
@@ -71,6 +81,7 @@ pub struct AudioAnalysisSection {
 
 ///[audio analysis](https://developer.spotify.com/web-api/get-audio-analysis/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysisMeta {
     pub analyzer_version: String,
     pub platform: String,
@@ -82,6 +93,7 @@ pub struct AudioAnalysisMeta {
 }
 ///[audio analysis](https://developer.spotify.com/web-api/get-audio-analysis/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysisSegment {
     pub start: f32,
     pub duration: f32,
@@ -96,6 +108,7 @@ pub struct AudioAnalysisSegment {
 
 ///[audio analysis](https://developer.spotify.com/web-api/get-audio-analysis/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysisTrack {
     pub num_samples: u32,
     pub duration: f32,
@@ -0,0 +1,158 @@
+//! All objects related to audiobooks and their chapters
+use super::album::Restrictions;
+use super::common::Extra;
+use super::common::{Copyright, ExternalUrls};
+use super::id::{AudiobookId, ChapterId};
+use super::image::Image;
+use super::page::Page;
+use super::release_date::ReleaseDate;
+use super::senum::{MediaType, Type};
+use super::show::ResumePoint;
+
+/// [Author object](https://developer.spotify.com/documentation/web-api/reference/get-an-audiobook)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Author {
+    pub name: String,
+}
+
+/// [Narrator object](https://developer.spotify.com/documentation/web-api/reference/get-an-audiobook)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Narrator {
+    pub name: String,
+}
+
+/// [Audiobook object simplified](https://developer.spotify.com/documentation/web-api/reference/get-users-saved-audiobooks)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimplifiedAudiobook {
+    pub authors: Vec<Author>,
+    pub available_markets: Vec<String>,
+    pub copyrights: Vec<Copyright>,
+    pub description: String,
+    pub html_description: String,
+    pub edition: Option<String>,
+    pub explicit: bool,
+    pub external_urls: ExternalUrls,
+    pub href: String,
+    pub id: AudiobookId,
+    pub images: Vec<Image>,
+    pub languages: Vec<String>,
+    pub media_type: MediaType,
+    pub name: String,
+    pub narrators: Vec<Narrator>,
+    pub publisher: String,
+    pub total_chapters: u32,
+    #[serde(rename = "type")]
+    pub type_: Type,
+    /// Spotify gives these `spotify:show:` URIs, so this isn't a typed `Uri`
+    pub uri: String,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
+}
+
+/// [Audiobook object full](https://developer.spotify.com/documentation/web-api/reference/get-an-audiobook)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FullAudiobook {
+    pub authors: Vec<Author>,
+    pub available_markets: Vec<String>,
+    pub chapters: Page<SimplifiedChapter>,
+    pub copyrights: Vec<Copyright>,
+    pub description: String,
+    pub html_description: String,
+    pub edition: Option<String>,
+    pub explicit: bool,
+    pub external_urls: ExternalUrls,
+    pub href: String,
+    pub id: AudiobookId,
+    pub images: Vec<Image>,
+    pub languages: Vec<String>,
+    pub media_type: MediaType,
+    pub name: String,
+    pub narrators: Vec<Narrator>,
+    pub publisher: String,
+    pub total_chapters: u32,
+    #[serde(rename = "type")]
+    pub type_: Type,
+    /// Spotify gives these `spotify:show:` URIs, so this isn't a typed `Uri`
+    pub uri: String,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct FullAudiobooks {
+    /// `None` in the position of any ID that wasn't found
+    pub audiobooks: Vec<Option<FullAudiobook>>,
+}
+
+/// [Chapter object simplified](https://developer.spotify.com/documentation/web-api/reference/get-audiobook-chapters)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimplifiedChapter {
+    pub audio_preview_url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub available_markets: Vec<String>,
+    pub chapter_number: u32,
+    pub description: String,
+    pub html_description: String,
+    pub duration_ms: u32,
+    pub explicit: bool,
+    pub external_urls: ExternalUrls,
+    pub href: String,
+    pub id: ChapterId,
+    pub images: Vec<Image>,
+    pub is_playable: Option<bool>,
+    pub languages: Vec<String>,
+    pub name: String,
+    #[serde(flatten)]
+    pub release_date: ReleaseDate,
+    pub resume_point: Option<ResumePoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
+    #[serde(rename = "type")]
+    pub type_: Type,
+    /// Spotify gives these `spotify:episode:` URIs, so this isn't a typed `Uri`
+    pub uri: String,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
+}
+
+/// [Chapter object full](https://developer.spotify.com/documentation/web-api/reference/get-a-chapter)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FullChapter {
+    pub audio_preview_url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub available_markets: Vec<String>,
+    pub audiobook: SimplifiedAudiobook,
+    pub chapter_number: u32,
+    pub description: String,
+    pub html_description: String,
+    pub duration_ms: u32,
+    pub explicit: bool,
+    pub external_urls: ExternalUrls,
+    pub href: String,
+    pub id: ChapterId,
+    pub images: Vec<Image>,
+    pub is_playable: Option<bool>,
+    pub languages: Vec<String>,
+    pub name: String,
+    #[serde(flatten)]
+    pub release_date: ReleaseDate,
+    pub resume_point: Option<ResumePoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
+    #[serde(rename = "type")]
+    pub type_: Type,
+    /// Spotify gives these `spotify:episode:` URIs, so this isn't a typed `Uri`
+    pub uri: String,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct FullChapters {
+    /// `None` in the position of any ID that wasn't found
+    pub chapters: Vec<Option<FullChapter>>,
+}
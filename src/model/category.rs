@@ -1,4 +1,5 @@
 //! All object related to category
+use super::common::Extra;
 use super::image::Image;
 use super::page::Page;
 /// category object
@@ -9,11 +10,14 @@ pub struct Category {
     pub icons: Vec<Image>,
     pub id: String,
     pub name: String,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// Categories wrapped by page object
 ///[get list categories](https://developer.spotify.com/web-api/get-list-categories/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PageCategory {
     pub categories: Page<Category>,
 }
@@ -0,0 +1,60 @@
+//! Small objects shared by several other models
+use super::senum::CopyrightType;
+use serde::{de, Deserialize, Deserializer};
+use std::collections::HashMap;
+
+/// Fields of a response that the model doesn't declare, kept so they survive re-serialization.
+/// With the `strict` feature such fields are rejected instead, so `extra` is always empty.
+pub type Extra = HashMap<String, serde_json::Value>;
+
+// Deserialize the `extra` map of a model, failing on the first field in it under `strict`
+pub(crate) fn extra<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Extra, D::Error> {
+    let extra = Extra::deserialize(deserializer)?;
+    match extra.keys().next() {
+        Some(field) if cfg!(feature = "strict") => {
+            Err(de::Error::custom(format_args!("unknown field `{}`", field)))
+        }
+        _ => Ok(extra),
+    }
+}
+
+///[copyright object](https://developer.spotify.com/documentation/web-api/reference/object-model/#copyright-object)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Copyright {
+    pub text: String,
+    #[serde(rename = "type")]
+    pub type_: CopyrightType,
+}
+
+///[external ID object](https://developer.spotify.com/documentation/web-api/reference/object-model/#external-id-object)
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ExternalIds {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ean: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upc: Option<String>,
+}
+
+///[external URL object](https://developer.spotify.com/documentation/web-api/reference/object-model/#external-url-object)
+/// Local tracks have no Spotify URL, so `spotify` may be missing. The object maps the type of
+/// each URL to the URL, so URLs of other types are kept in `extra`, even under `strict`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExternalUrls {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spotify: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+
+///[followers object](https://developer.spotify.com/documentation/web-api/reference/object-model/#followers-object)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Followers {
+    /// Always `None`; Spotify doesn't currently support retrieving the list of followers
+    pub href: Option<String>,
+    pub total: u32,
+}
@@ -1,6 +1,8 @@
 //! All objects related to context
 use std::collections::HashMap;
 
+use super::common::ExternalUrls;
+use super::common::Extra;
 use super::device::Device;
 use super::senum::PlayingItem;
 use super::senum::{CurrentlyPlayingType, DisallowKey, RepeatState, Type};
@@ -8,10 +10,12 @@ use super::track::FullTrack;
 /// Context object
 ///[get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Context {
+    /// An album, artist, playlist or show, depending on `type_`
     pub uri: String,
     pub href: String,
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     #[serde(rename = "type")]
     pub type_: Type,
 }
@@ -19,6 +23,7 @@ pub struct Context {
 /// Full playing context
 ///[get information about the users current playback](https://developer.spotify.com/web-api/get-information-about-the-users-current-playback/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullPlayingContext {
     pub device: Device,
     pub repeat_state: RepeatState,
@@ -32,6 +37,7 @@ pub struct FullPlayingContext {
 
 ///[get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SimplifiedPlayingContext {
     pub context: Option<Context>,
     pub timestamp: u64,
@@ -50,6 +56,8 @@ pub struct CurrentlyPlayingContext {
     pub item: Option<PlayingItem>,
     pub currently_playing_type: CurrentlyPlayingType,
     pub actions: Actions,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 /// [Currently Playback Context](https://developer.spotify.com/documentation/web-api/reference/player/get-information-about-the-users-current-playback/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -64,10 +72,13 @@ pub struct CurrentlyPlaybackContext {
     pub item: Option<PlayingItem>,
     pub currently_playing_type: CurrentlyPlayingType,
     pub actions: Actions,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// [actions](https://developer.spotify.com/documentation/web-api/reference/player/get-the-users-currently-playing-track/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Actions {
     pub disallows: HashMap<DisallowKey, bool>,
 }
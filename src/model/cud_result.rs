@@ -1,5 +1,6 @@
 //! the result of post/put/delete request
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CUDResult {
     pub snapshot_id: String,
 }
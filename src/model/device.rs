@@ -1,18 +1,24 @@
+use super::common::Extra;
 /// All objects related to device
 ///[get a users available devices](https://developer.spotify.com/web-api/get-a-users-available-devices/)
 use super::senum::DeviceType;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Device {
-    pub id: String,
+    pub id: Option<String>,
     pub is_active: bool,
+    pub is_private_session: bool,
     pub is_restricted: bool,
     pub name: String,
+    pub supports_volume: bool,
     #[serde(rename = "type")]
     pub type_: DeviceType,
-    pub volume_percent: u32,
+    pub volume_percent: Option<u32>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct DevicePayload {
     pub devices: Vec<Device>,
 }
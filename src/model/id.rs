@@ -0,0 +1,310 @@
+//! Typed Spotify IDs, and conversion to and from URIs and open.spotify.com URLs
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum IdError {
+    #[error("{0:?} is not a valid Spotify ID")]
+    InvalidId(String),
+
+    #[error("{0:?} is not a Spotify URI")]
+    InvalidUri(String),
+
+    #[error("{0:?} is not an open.spotify.com URL")]
+    InvalidUrl(String),
+
+    #[error("expected a {expected} ID, but found a {found} ID")]
+    WrongType {
+        expected: &'static str,
+        found: String,
+    },
+}
+
+/// Common behavior of typed IDs. `KIND` is the item type as it appears in URIs and URLs.
+///
+/// ```rust
+/// use spotify_api::model::id::{AlbumId, Id, TrackId};
+///
+/// let url = "https://open.spotify.com/album/4aawyAB9vmqN3uQ7FjRGTy?si=abc123";
+/// let album: AlbumId = url.parse().unwrap();
+/// assert_eq!(album.uri(), "spotify:album:4aawyAB9vmqN3uQ7FjRGTy");
+/// assert!(url.parse::<TrackId>().is_err());
+/// ```
+pub trait Id: Sized + AsRef<str> + Borrow<str> + fmt::Display {
+    const KIND: &'static str;
+
+    /// Create an ID from its bare form, without a URI or URL prefix.
+    fn from_id<S: Into<String>>(id: S) -> Result<Self, IdError>;
+
+    fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+
+    /// Parse an ID from a URI like `spotify:track:6rqhFgbbKwnb9MLmUQDhG6`.
+    fn from_uri(uri: &str) -> Result<Self, IdError> {
+        let (kind, id) = split_uri(uri).ok_or_else(|| IdError::InvalidUri(uri.to_owned()))?;
+        check_kind::<Self>(kind)?;
+        Self::from_id(id)
+    }
+
+    /// Parse an ID from a link like `https://open.spotify.com/track/6rqhFgbbKwnb9MLmUQDhG6?si=...`.
+    fn from_url(url: &str) -> Result<Self, IdError> {
+        let (kind, id) = split_url(url).ok_or_else(|| IdError::InvalidUrl(url.to_owned()))?;
+        check_kind::<Self>(kind)?;
+        Self::from_id(id)
+    }
+
+    fn uri(&self) -> String {
+        format!("spotify:{}:{}", Self::KIND, self.as_str())
+    }
+
+    fn url(&self) -> String {
+        format!("https://open.spotify.com/{}/{}", Self::KIND, self.as_str())
+    }
+}
+
+fn check_kind<I: Id>(kind: &str) -> Result<(), IdError> {
+    if kind == I::KIND {
+        Ok(())
+    } else {
+        Err(IdError::WrongType {
+            expected: I::KIND,
+            found: kind.to_owned(),
+        })
+    }
+}
+
+// Playlists used to be addressed through their owner, as in `spotify:user:{user}:playlist:{id}`
+// and `/user/{user}/playlist/{id}`; those are still found in the wild.
+fn split_parts<'a>(parts: &[&'a str]) -> Option<(&'a str, &'a str)> {
+    match *parts {
+        [kind, id] => Some((kind, id)),
+        ["user", _, "playlist", id] => Some(("playlist", id)),
+        _ => None,
+    }
+}
+
+fn split_uri(uri: &str) -> Option<(&str, &str)> {
+    let rest = uri.strip_prefix("spotify:")?;
+    split_parts(&rest.split(':').collect::<Vec<_>>())
+}
+
+fn split_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("open.spotify.com/")?;
+    // Arrays of chars are only patterns since Rust 1.51
+    #[allow(clippy::manual_pattern_char_comparison)]
+    let path = rest.split(|c| c == '?' || c == '#').next()?;
+
+    let mut segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    // Localized and embedded links carry an extra leading segment, like `/intl-de/track/...`
+    if let Some(first) = segments.first() {
+        if first.starts_with("intl-") || *first == "embed" {
+            segments.remove(0);
+        }
+    }
+    split_parts(&segments)
+}
+
+fn is_base62(id: &str) -> bool {
+    id.len() == 22 && id.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+// User IDs are usernames for older accounts, so they can't be held to the base62 format
+fn is_user_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id
+            .chars()
+            .any(|c| c.is_whitespace() || c == ':' || c == '/' || c == '?' || c == '#')
+}
+
+macro_rules! define_id {
+    ($(#[$meta:meta])* $name:ident, $kind:literal, $validate:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl Id for $name {
+            const KIND: &'static str = $kind;
+
+            fn from_id<S: Into<String>>(id: S) -> Result<Self, IdError> {
+                let id = id.into();
+                if $validate(&id) {
+                    Ok($name(id))
+                } else {
+                    Err(IdError::InvalidId(id))
+                }
+            }
+        }
+
+        /// Accepts a bare ID, a URI or an open.spotify.com URL.
+        impl FromStr for $name {
+            type Err = IdError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if s.starts_with("spotify:") {
+                    Self::from_uri(s)
+                } else if s.starts_with("https://") || s.starts_with("http://") {
+                    Self::from_url(s)
+                } else {
+                    Self::from_id(s)
+                }
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = IdError;
+            fn try_from(s: String) -> Result<Self, Self::Error> {
+                s.parse()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+define_id!(
+    /// ID of an album
+    AlbumId,
+    "album",
+    is_base62
+);
+define_id!(
+    /// ID of an artist
+    ArtistId,
+    "artist",
+    is_base62
+);
+define_id!(
+    /// ID of an audiobook
+    AudiobookId,
+    "audiobook",
+    is_base62
+);
+define_id!(
+    /// ID of an audiobook chapter
+    ChapterId,
+    "chapter",
+    is_base62
+);
+define_id!(
+    /// ID of a podcast episode
+    EpisodeId,
+    "episode",
+    is_base62
+);
+define_id!(
+    /// ID of a playlist
+    PlaylistId,
+    "playlist",
+    is_base62
+);
+define_id!(
+    /// ID of a podcast show
+    ShowId,
+    "show",
+    is_base62
+);
+define_id!(
+    /// ID of a track
+    TrackId,
+    "track",
+    is_base62
+);
+define_id!(
+    /// ID of a user. Older accounts use their username, so these aren't always base62.
+    UserId,
+    "user",
+    is_user_id
+);
+
+/// The URI of an item whose ID is an `I`, like `spotify:album:4aawyAB9vmqN3uQ7FjRGTy`. Only URIs
+/// of the matching kind are accepted. Only the ID is kept, so URIs always serialize as
+/// `spotify:{kind}:{id}`, and legacy playlist URIs lose their `user:{user}:` part.
+///
+/// ```rust
+/// use spotify_api::model::id::{AlbumId, PlaylistId, Uri};
+///
+/// let uri: Uri<AlbumId> = "spotify:album:4aawyAB9vmqN3uQ7FjRGTy".parse().unwrap();
+/// assert_eq!(uri.id().as_ref(), "4aawyAB9vmqN3uQ7FjRGTy");
+/// assert_eq!(uri.to_string(), "spotify:album:4aawyAB9vmqN3uQ7FjRGTy");
+///
+/// let legacy = "spotify:user:spotify:playlist:37i9dQZF1DXcBWIGoYBM5M";
+/// let uri: Uri<PlaylistId> = legacy.parse().unwrap();
+/// assert_eq!(uri.to_string(), "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String", bound = "I: Id + Clone")]
+pub struct Uri<I>(I);
+
+impl<I: Id> Uri<I> {
+    pub fn id(&self) -> &I {
+        &self.0
+    }
+
+    pub fn into_id(self) -> I {
+        self.0
+    }
+}
+
+impl<I: Id> From<I> for Uri<I> {
+    fn from(id: I) -> Self {
+        Uri(id)
+    }
+}
+
+impl<I: Id> FromStr for Uri<I> {
+    type Err = IdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        I::from_uri(s).map(Uri)
+    }
+}
+
+impl<I: Id> TryFrom<String> for Uri<I> {
+    type Error = IdError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<I: Id> From<Uri<I>> for String {
+    fn from(uri: Uri<I>) -> Self {
+        uri.to_string()
+    }
+}
+
+impl<I: Id> fmt::Display for Uri<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "spotify:{}:{}", I::KIND, self.0.as_str())
+    }
+}
@@ -1,6 +1,7 @@
 //! Image object
 ///[image object](https://developer.spotify.com/web-api/object-model/#image-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Image {
     pub height: Option<u32>,
     pub url: String,
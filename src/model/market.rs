@@ -0,0 +1,7 @@
+//! Markets object
+///[available markets](https://developer.spotify.com/documentation/web-api/reference/markets/get-available-markets/)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Markets {
+    pub markets: Vec<String>,
+}
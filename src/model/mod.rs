@@ -2,16 +2,21 @@
 pub mod album;
 pub mod artist;
 pub mod audio;
+pub mod audiobook;
 pub mod category;
+pub mod common;
 pub mod context;
 pub mod cud_result;
 pub mod device;
+pub mod id;
 pub mod image;
+pub mod market;
 pub mod offset;
 pub mod page;
 pub mod playing;
 pub mod playlist;
 pub mod recommend;
+pub mod release_date;
 pub mod search;
 pub mod senum;
 pub mod show;
@@ -1,6 +1,7 @@
 //! Offset object
 ///[offset object](https://developer.spotify.com/documentation/web-api/reference/player/start-a-users-playback/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Offset {
     pub position: Option<u32>,
     pub uri: Option<String>,
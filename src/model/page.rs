@@ -2,6 +2,7 @@
 ///Basic page
 ///ppaging abject(https://developer.spotify.com/web-api/object-model/#paging-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Page<T> {
     pub href: String,
     pub items: Vec<T>,
@@ -15,6 +16,7 @@ pub struct Page<T> {
 /// cursor based page
 ///[cursor based paging object](https://developer.spotify.com/web-api/object-model/#cursor-based-paging-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CursorBasedPage<T> {
     pub href: String,
     pub items: Vec<T>,
@@ -29,8 +31,10 @@ pub struct CursorBasedPage<T> {
 ///Cursor object
 ///[cursor object](https://developer.spotify.com/web-api/object-model/#cursor-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Cursor {
     pub after: Option<String>,
+    pub before: Option<String>,
 }
 
 #[cfg(feature = "api")]
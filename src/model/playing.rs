@@ -1,12 +1,14 @@
 //! All kinds of play object
 
 use super::context::Context;
+use super::senum::PlayingItem;
 use super::timestamp::Timestamp;
 use super::track::FullTrack;
 use super::track::SimplifiedTrack;
 /// current playing track
 ///[get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Playing {
     pub context: Option<Context>,
     pub timestamp: u64,
@@ -18,8 +20,18 @@ pub struct Playing {
 /// playing history
 ///[play history object](https://developer.spotify.com/web-api/object-model/#play-history-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PlayHistory {
     pub track: SimplifiedTrack,
     pub played_at: Timestamp,
     pub context: Option<Context>,
 }
+
+/// The user's playback queue
+///[get the user's queue](https://developer.spotify.com/documentation/web-api/reference/get-queue)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Queue {
+    pub currently_playing: Option<PlayingItem>,
+    pub queue: Vec<PlayingItem>,
+}
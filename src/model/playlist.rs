@@ -1,7 +1,8 @@
 //! All kinds of playlists objects
-use serde_json::Value;
-use std::collections::HashMap;
 
+use super::common::Extra;
+use super::common::{ExternalUrls, Followers};
+use super::id::{PlaylistId, Uri};
 use super::image::Image;
 use super::page::Page;
 use super::senum::Type;
@@ -12,28 +13,31 @@ use super::user::PublicUser;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimplifiedPlaylist {
     pub collaborative: bool,
-    pub external_urls: HashMap<String, String>,
+    pub description: Option<String>,
+    pub external_urls: ExternalUrls,
     pub href: String,
-    pub id: String,
+    pub id: PlaylistId,
     pub images: Vec<Image>,
     pub name: String,
     pub owner: PublicUser,
     pub public: Option<bool>,
     pub snapshot_id: String,
-    pub tracks: HashMap<String, Value>,
+    pub tracks: PlaylistTracksRef,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: String,
+    pub uri: Uri<PlaylistId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FullPlaylist {
     pub collaborative: bool,
-    pub description: String,
-    pub external_urls: HashMap<String, String>,
-    pub followers: Option<HashMap<String, Value>>,
+    pub description: Option<String>,
+    pub external_urls: ExternalUrls,
+    pub followers: Followers,
     pub href: String,
-    pub id: String,
+    pub id: PlaylistId,
     pub images: Vec<Image>,
     pub name: String,
     pub owner: PublicUser,
@@ -42,7 +46,18 @@ pub struct FullPlaylist {
     pub tracks: Page<PlaylistTrack>,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: String,
+    pub uri: Uri<PlaylistId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
+}
+
+/// Reference to the items of a playlist, which must be fetched separately
+///[playlist tracks reference](https://developer.spotify.com/documentation/web-api/reference/object-model/#playlist-object-simplified)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PlaylistTracksRef {
+    pub href: String,
+    pub total: u32,
 }
 
 ///[playlist track object](https://developer.spotify.com/web-api/object-model/#playlist-track-object)
@@ -52,9 +67,12 @@ pub struct PlaylistTrack {
     pub added_by: Option<PublicUser>,
     pub is_local: bool,
     pub track: Option<FullTrack>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 ///[get list featured playlists](https://developer.spotify.com/web-api/get-list-featured-playlists/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FeaturedPlaylists {
     pub message: String,
     pub playlists: Page<SimplifiedPlaylist>,
@@ -1,13 +1,22 @@
 //! All objects related to recommendation
+pub use super::senum::RecommendationsSeedType;
 use super::track::SimplifiedTrack;
 ///[recommendations object](https://developer.spotify.com/web-api/object-model/#recommendations-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Recommendations {
     pub seeds: Vec<RecommendationsSeed>,
     pub tracks: Vec<SimplifiedTrack>,
 }
+///[available genre seeds](https://developer.spotify.com/documentation/web-api/reference/browse/get-recommendation-genres/)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct GenreSeeds {
+    pub genres: Vec<String>,
+}
 ///[recommendations seed object](https://developer.spotify.com/web-api/object-model/#recommendations-seed-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct RecommendationsSeed {
     #[serde(rename = "afterFilteringSize")]
     pub after_filtering_size: u32,
@@ -20,15 +29,3 @@ pub struct RecommendationsSeed {
     #[serde(rename = "type")]
     pub type_: RecommendationsSeedType,
 }
-
-// TODO: The Spotify API returns these as lowercase.
-// Do we need a custom deserialize from implementation?
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum RecommendationsSeedType {
-    #[serde(rename = "ARTIST")]
-    Artist,
-    #[serde(rename = "TRACK")]
-    Track,
-    #[serde(rename = "GENRE")]
-    Genre,
-}
@@ -0,0 +1,174 @@
+//! Release dates, which Spotify reports with varying precision
+use super::timestamp::days_in_month;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{date:?} is not a release date with {precision:?} precision")]
+pub struct InvalidReleaseDate {
+    date: String,
+    precision: String,
+}
+
+/// Release date of an album, episode or chapter, built from the `release_date` and
+/// `release_date_precision` fields.
+///
+/// Dates are ordered chronologically, with a less precise date sorting before any more precise
+/// date within the same period.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "RawReleaseDate", into = "RawReleaseDate")]
+pub enum ReleaseDate {
+    Year(i32),
+    Month(i32, u8),
+    Day(i32, u8, u8),
+}
+
+impl ReleaseDate {
+    pub fn year(&self) -> i32 {
+        match *self {
+            ReleaseDate::Year(y) | ReleaseDate::Month(y, _) | ReleaseDate::Day(y, _, _) => y,
+        }
+    }
+
+    pub fn month(&self) -> Option<u8> {
+        match *self {
+            ReleaseDate::Year(_) => None,
+            ReleaseDate::Month(_, m) | ReleaseDate::Day(_, m, _) => Some(m),
+        }
+    }
+
+    pub fn day(&self) -> Option<u8> {
+        match *self {
+            ReleaseDate::Day(_, _, d) => Some(d),
+            _ => None,
+        }
+    }
+
+    pub fn precision(&self) -> &'static str {
+        match *self {
+            ReleaseDate::Year(_) => "year",
+            ReleaseDate::Month(..) => "month",
+            ReleaseDate::Day(..) => "day",
+        }
+    }
+
+    fn key(&self) -> (i32, u8, u8) {
+        (
+            self.year(),
+            self.month().unwrap_or_default(),
+            self.day().unwrap_or_default(),
+        )
+    }
+}
+
+impl PartialOrd for ReleaseDate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ReleaseDate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl fmt::Display for ReleaseDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ReleaseDate::Year(y) => write!(f, "{:04}", y),
+            ReleaseDate::Month(y, m) => write!(f, "{:04}-{:02}", y, m),
+            ReleaseDate::Day(y, m, d) => write!(f, "{:04}-{:02}-{:02}", y, m, d),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawReleaseDate {
+    release_date: String,
+    release_date_precision: String,
+}
+
+impl TryFrom<RawReleaseDate> for ReleaseDate {
+    type Error = InvalidReleaseDate;
+    fn try_from(raw: RawReleaseDate) -> Result<Self, Self::Error> {
+        let parts = raw.release_date.split('-').collect::<Vec<_>>();
+        let parsed = match (raw.release_date_precision.as_str(), parts.as_slice()) {
+            ("year", [y]) => y.parse().ok().map(ReleaseDate::Year),
+            ("month", [y, m]) => match (y.parse(), m.parse()) {
+                (Ok(y), Ok(m)) if (1..=12).contains(&m) => Some(ReleaseDate::Month(y, m)),
+                _ => None,
+            },
+            ("day", [y, m, d]) => match (y.parse::<i32>(), m.parse::<u8>(), d.parse::<u8>()) {
+                (Ok(y), Ok(m), Ok(d))
+                    if (1..=12).contains(&m)
+                        && d >= 1
+                        && u32::from(d) <= days_in_month(y.into(), m.into()) =>
+                {
+                    Some(ReleaseDate::Day(y, m, d))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        parsed.ok_or(InvalidReleaseDate {
+            date: raw.release_date,
+            precision: raw.release_date_precision,
+        })
+    }
+}
+
+impl From<ReleaseDate> for RawReleaseDate {
+    fn from(date: ReleaseDate) -> Self {
+        RawReleaseDate {
+            release_date: date.to_string(),
+            release_date_precision: date.precision().to_owned(),
+        }
+    }
+}
+
+/// Release dates that may be missing altogether, as for the albums of local files. Use with
+/// `#[serde(flatten, with = "...")]`: a date that is present but invalid is an error, rather
+/// than being read as `None`.
+pub(crate) mod optional {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct RawOptionalReleaseDate {
+        release_date: Option<String>,
+        release_date_precision: Option<String>,
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<ReleaseDate>, D::Error> {
+        let raw = RawOptionalReleaseDate::deserialize(deserializer)?;
+        match (raw.release_date, raw.release_date_precision) {
+            (None, None) => Ok(None),
+            (Some(release_date), Some(release_date_precision)) => {
+                let raw = RawReleaseDate {
+                    release_date,
+                    release_date_precision,
+                };
+                ReleaseDate::try_from(raw)
+                    .map(Some)
+                    .map_err(de::Error::custom)
+            }
+            _ => Err(de::Error::custom(
+                "release_date and release_date_precision must be given together",
+            )),
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        date: &Option<ReleaseDate>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        date.map(RawReleaseDate::from).serialize(serializer)
+    }
+}
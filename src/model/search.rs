@@ -1,42 +1,55 @@
 //! All object related to search
 use super::album::SimplifiedAlbum;
 use super::artist::FullArtist;
+use super::audiobook::SimplifiedAudiobook;
 use super::page::Page;
 use super::playlist::SimplifiedPlaylist;
 use super::show::{SimplifiedEpisode, SimplifiedShow};
 use super::track::FullTrack;
 ///[search item](https://developer.spotify.com/web-api/search-item/);
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchPlaylists {
     pub playlists: Page<SimplifiedPlaylist>,
 }
 ///[search item](https://developer.spotify.com/web-api/search-item/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchAlbums {
     pub albums: Page<SimplifiedAlbum>,
 }
 
 ///[search item](https://developer.spotify.com/web-api/search-item/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchArtists {
     pub artists: Page<FullArtist>,
 }
 ///[search item](https://developer.spotify.com/web-api/search-item/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchTracks {
     pub tracks: Page<FullTrack>,
 }
 
 ///[search item](https://developer.spotify.com/web-api/search-item/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchShows {
     pub shows: Page<SimplifiedShow>,
 }
 ///[search item](https://developer.spotify.com/web-api/search-item/)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchEpisodes {
     pub episodes: Page<SimplifiedEpisode>,
 }
+///[search item](https://developer.spotify.com/web-api/search-item/)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SearchAudiobooks {
+    pub audiobooks: Page<SimplifiedAudiobook>,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SearchResult {
@@ -52,4 +65,27 @@ pub enum SearchResult {
     Shows(Page<SimplifiedShow>),
     #[serde(rename = "episodes")]
     Episodes(Page<SimplifiedEpisode>),
+    #[serde(rename = "audiobooks")]
+    Audiobooks(Page<SimplifiedAudiobook>),
+}
+
+/// Results of a search across one or more types; only the requested types are present.
+///[search for an item](https://developer.spotify.com/documentation/web-api/reference/search/search/)
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SearchResults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub albums: Option<Page<SimplifiedAlbum>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artists: Option<Page<FullArtist>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audiobooks: Option<Page<SimplifiedAudiobook>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub episodes: Option<Page<SimplifiedEpisode>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playlists: Option<Page<SimplifiedPlaylist>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shows: Option<Page<SimplifiedShow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracks: Option<Page<FullTrack>>,
 }
@@ -1,391 +1,285 @@
 use super::show;
 use super::track;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::convert::Infallible;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
-use thiserror::Error;
 
-#[derive(Debug, Error)]
-#[error("unrecognized enum value {0}")]
-pub struct Unrecognized(String);
-
-// TODO: Instead of implementing an `as_str` method, would `AsRef<str>` make more sense?
+// Defines an enum of the string values Spotify documents for a field. Values Spotify adds later
+// are kept in an `Unknown` variant instead of failing deserialization, and round-trip unchanged.
+// Additional accepted spellings of a value can be listed after it, separated by `|`; the first
+// spelling is the one serialized. Values are compared and hashed by their text, so an `Unknown`
+// holding the text of a known value is equal to that value.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident => $value:literal $(| $alias:literal)*,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug)]
+        pub enum $name {
+            $($variant,)*
+            /// A value not known to this version of the crate
+            Unknown(String),
+        }
 
-/// Album type - ‘album’, ‘single’, ‘appears_on’, ‘compilation’
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum AlbumType {
-    Album,
-    Single,
-    AppearsOn,
-    Compilation,
-}
-impl FromStr for AlbumType {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "album" => Ok(AlbumType::Album),
-            "single" => Ok(AlbumType::Single),
-            "appears_on" => Ok(AlbumType::AppearsOn),
-            "compilation" => Ok(AlbumType::Compilation),
-            _ => Err(Unrecognized(s.to_owned())),
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(v) => v.as_str(),
+                }
+            }
         }
-    }
-}
-impl AlbumType {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            AlbumType::Album => "album",
-            AlbumType::Single => "single",
-            AlbumType::AppearsOn => "appears_on",
-            AlbumType::Compilation => "compilation",
+
+        impl FromStr for $name {
+            type Err = Infallible;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(match s {
+                    $($value $(| $alias)* => $name::$variant,)*
+                    _ => $name::Unknown(s.to_owned()),
+                })
+            }
         }
-    }
-}
 
-///  Type: ‘artist’, ‘album’,‘track’, ‘playlist’, 'show' or 'episode'
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum Type {
-    Artist,
-    Album,
-    Track,
-    Playlist,
-    User,
-    Show,
-    Episode,
-}
-impl Type {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            Type::Album => "album",
-            Type::Artist => "artist",
-            Type::Track => "track",
-            Type::Playlist => "playlist",
-            Type::User => "user",
-            Type::Show => "show",
-            Type::Episode => "episode",
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.as_str() == other.as_str()
+            }
         }
-    }
-}
-impl FromStr for Type {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "artist" => Ok(Type::Artist),
-            "album" => Ok(Type::Album),
-            "track" => Ok(Type::Track),
-            "playlist" => Ok(Type::Playlist),
-            "user" => Ok(Type::User),
-            "show" => Ok(Type::Show),
-            "episode" => Ok(Type::Episode),
-            _ => Err(Unrecognized(s.to_owned())),
+
+        impl Eq for $name {}
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.as_str().hash(state)
+            }
         }
-    }
-}
 
-/// additional_typs: track, episode
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum AdditionalType {
-    Track,
-    Episode,
-}
-impl AdditionalType {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            AdditionalType::Track => "track",
-            AdditionalType::Episode => "episode",
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
         }
-    }
-}
-impl FromStr for AdditionalType {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "track" => Ok(AdditionalType::Track),
-            "episode" => Ok(AdditionalType::Episode),
-            _ => Err(Unrecognized(s.to_owned())),
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
         }
-    }
-}
-/// currently_playing_type: track, episode, ad, unknown.
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum CurrentlyPlayingType {
-    Track,
-    Episode,
-    Advertisement,
-    Unknown,
-}
-impl CurrentlyPlayingType {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            CurrentlyPlayingType::Track => "track",
-            CurrentlyPlayingType::Episode => "episode",
-            CurrentlyPlayingType::Advertisement => "ad",
-            CurrentlyPlayingType::Unknown => "unknown",
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
         }
-    }
-}
-impl FromStr for CurrentlyPlayingType {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "track" => Ok(CurrentlyPlayingType::Track),
-            "episode" => Ok(CurrentlyPlayingType::Episode),
-            "ad" => Ok(CurrentlyPlayingType::Advertisement),
-            "unknown" => Ok(CurrentlyPlayingType::Unknown),
-            _ => Err(Unrecognized(s.to_owned())),
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                // UNWRAP: Parsing is infallible
+                Ok(s.parse().unwrap())
+            }
         }
-    }
+    };
 }
 
-/// disallow: interrupting_playback, pausing, resuming, seeking, skipping_next, skipping_prev, toggling_repeat_context, toggling_shuffle, toggling_repeat_track, transferring_playback
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, Hash)]
-#[serde(rename_all = "snake_case")]
-pub enum DisallowKey {
-    InterruptingPlayback,
-    Pausing,
-    Resuming,
-    Seeking,
-    SkippingNext,
-    SkippingPrev,
-    TogglingRepeatContext,
-    TogglingShuffle,
-    TogglingRepeatTrack,
-    TransferringPlayback,
-}
-impl DisallowKey {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            DisallowKey::InterruptingPlayback => "interrupting_playback",
-            DisallowKey::Pausing => "pausing",
-            DisallowKey::Resuming => "resuming",
-            DisallowKey::Seeking => "seeking",
-            DisallowKey::SkippingNext => "skipping_next",
-            DisallowKey::SkippingPrev => "skipping_prev",
-            DisallowKey::TogglingRepeatContext => "toggling_repeat_context",
-            DisallowKey::TogglingShuffle => "toggling_shuffle",
-            DisallowKey::TogglingRepeatTrack => "toggling_repeat_track",
-            DisallowKey::TransferringPlayback => "transferring_playback",
-        }
+string_enum! {
+    /// Album type - ‘album’, ‘single’, ‘appears_on’, ‘compilation’
+    pub enum AlbumType {
+        Album => "album",
+        Single => "single",
+        AppearsOn => "appears_on",
+        Compilation => "compilation",
     }
 }
-impl FromStr for DisallowKey {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "interrupting_playback" => Ok(DisallowKey::InterruptingPlayback),
-            "pausing" => Ok(DisallowKey::Pausing),
-            "resuming" => Ok(DisallowKey::Resuming),
-            "seeking" => Ok(DisallowKey::Seeking),
-            "skipping_next" => Ok(DisallowKey::SkippingNext),
-            "skipping_prev" => Ok(DisallowKey::SkippingPrev),
-            "toggling_repeat_context" => Ok(DisallowKey::TogglingRepeatContext),
-            "toggling_shuffle" => Ok(DisallowKey::TogglingShuffle),
-            "toggling_repeat_track" => Ok(DisallowKey::TogglingRepeatTrack),
-            "transferring_playback" => Ok(DisallowKey::TransferringPlayback),
-            _ => Err(Unrecognized(s.to_owned())),
-        }
+
+string_enum! {
+    ///  Type: ‘artist’, ‘album’,‘track’, ‘playlist’, 'show', 'episode', 'audiobook', 'chapter' or
+    /// 'audio_features'
+    pub enum Type {
+        Artist => "artist",
+        Album => "album",
+        Track => "track",
+        Playlist => "playlist",
+        User => "user",
+        Show => "show",
+        Episode => "episode",
+        Audiobook => "audiobook",
+        Chapter => "chapter",
+        AudioFeatures => "audio_features",
     }
 }
 
-/// time range: long-term, medium-term, short-term
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum TimeRange {
-    LongTerm,
-    MediumTerm,
-    ShortTerm,
+string_enum! {
+    /// copyright type: C = the copyright, P = the sound recording (performance) copyright
+    pub enum CopyrightType {
+        Copyright => "C",
+        Performance => "P",
+    }
 }
 
-impl TimeRange {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            TimeRange::LongTerm => "long_term",
-            TimeRange::MediumTerm => "medium_term",
-            TimeRange::ShortTerm => "short_term",
-        }
+string_enum! {
+    /// media type of shows and audiobooks: audio, video, mixed
+    pub enum MediaType {
+        Audio => "audio",
+        Video => "video",
+        Mixed => "mixed",
     }
 }
 
-impl FromStr for TimeRange {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "long_term" => Ok(TimeRange::LongTerm),
-            "medium_term" => Ok(TimeRange::MediumTerm),
-            "short_term" => Ok(TimeRange::ShortTerm),
-            _ => Err(Unrecognized(s.to_owned())),
-        }
+string_enum! {
+    /// additional_typs: track, episode
+    pub enum AdditionalType {
+        Track => "track",
+        Episode => "episode",
     }
 }
 
-///repeat state: track, context or off.
-/// - track will repeat the current track.
-/// - context will repeat the current context.
-/// - off will turn repeat off.
-#[derive(Clone, Debug, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub enum RepeatState {
-    Off,
-    Track,
-    Context,
-}
-impl RepeatState {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            RepeatState::Off => "off",
-            RepeatState::Track => "track",
-            RepeatState::Context => "context",
-        }
+string_enum! {
+    /// currently_playing_type: track, episode, ad, unknown.
+    ///
+    /// Spotify's own `unknown` value is kept as an `Unknown` value.
+    pub enum CurrentlyPlayingType {
+        Track => "track",
+        Episode => "episode",
+        Advertisement => "ad",
     }
 }
-impl FromStr for RepeatState {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "off" => Ok(RepeatState::Off),
-            "track" => Ok(RepeatState::Track),
-            "context" => Ok(RepeatState::Context),
-            _ => Err(Unrecognized(s.to_owned())),
-        }
+
+string_enum! {
+    /// disallow: interrupting_playback, pausing, resuming, seeking, skipping_next, skipping_prev, toggling_repeat_context, toggling_shuffle, toggling_repeat_track, transferring_playback
+    pub enum DisallowKey {
+        InterruptingPlayback => "interrupting_playback",
+        Pausing => "pausing",
+        Resuming => "resuming",
+        Seeking => "seeking",
+        SkippingNext => "skipping_next",
+        SkippingPrev => "skipping_prev",
+        TogglingRepeatContext => "toggling_repeat_context",
+        TogglingShuffle => "toggling_shuffle",
+        TogglingRepeatTrack => "toggling_repeat_track",
+        TransferringPlayback => "transferring_playback",
     }
 }
 
-/// Type for include_external: audio
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum IncludeExternal {
-    Audio,
-}
-impl IncludeExternal {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            IncludeExternal::Audio => "audio",
-        }
+string_enum! {
+    /// The user's subscription level: premium, free or open
+    pub enum Product {
+        Premium => "premium",
+        Free => "free",
+        Open => "open",
     }
 }
-impl FromStr for IncludeExternal {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "audio" => Ok(IncludeExternal::Audio),
-            _ => Err(Unrecognized(s.to_owned())),
-        }
+
+string_enum! {
+    /// time range: long-term, medium-term, short-term
+    pub enum TimeRange {
+        LongTerm => "long_term",
+        MediumTerm => "medium_term",
+        ShortTerm => "short_term",
     }
 }
 
-/// Type for search: artist, album, track, playlist, show, episode
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug)]
-#[serde(rename_all = "snake_case")]
-pub enum SearchType {
-    Artist,
-    Album,
-    Track,
-    Playlist,
-    Show,
-    Episode,
+string_enum! {
+    /// top item type: artists, tracks
+    pub enum TopItemType {
+        Artists => "artists",
+        Tracks => "tracks",
+    }
 }
 
-impl SearchType {
-    pub fn as_str(&self) -> &str {
-        match *self {
-            SearchType::Album => "album",
-            SearchType::Artist => "artist",
-            SearchType::Track => "track",
-            SearchType::Playlist => "playlist",
-            SearchType::Show => "show",
-            SearchType::Episode => "episode",
-        }
+string_enum! {
+    ///repeat state: track, context or off.
+    /// - track will repeat the current track.
+    /// - context will repeat the current context.
+    /// - off will turn repeat off.
+    pub enum RepeatState {
+        Off => "off",
+        Track => "track",
+        Context => "context",
     }
 }
-impl FromStr for SearchType {
-    type Err = Unrecognized;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "artist" => Ok(SearchType::Artist),
-            "album" => Ok(SearchType::Album),
-            "track" => Ok(SearchType::Track),
-            "playlist" => Ok(SearchType::Playlist),
-            "show" => Ok(SearchType::Show),
-            "episode" => Ok(SearchType::Episode),
-            _ => Err(Unrecognized(s.to_owned())),
-        }
+
+string_enum! {
+    /// Type for include_external: audio
+    pub enum IncludeExternal {
+        Audio => "audio",
     }
 }
 
-/// Device Type: computer, smartphone, speaker, TV, etc.
-/// See the [Spotify developer
-/// docs](https://developer.spotify.com/documentation/web-api/reference/player/get-a-users-available-devices/#device-types)
-/// for more information, or in case we are missing a device type here.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum DeviceType {
-    Computer,
-    Tablet,
-    Smartphone,
-    Speaker,
-    TV,
-    AVR,
-    STB,
-    AudioDongle,
-    GameConsole,
-    CastVideo,
-    CastAudio,
-    Automobile,
-    Unknown,
+string_enum! {
+    /// Type for search: artist, album, track, playlist, show, episode, audiobook
+    pub enum SearchType {
+        Artist => "artist",
+        Album => "album",
+        Track => "track",
+        Playlist => "playlist",
+        Show => "show",
+        Episode => "episode",
+        Audiobook => "audiobook",
+    }
 }
 
-pub enum Scope {
-    AppRemoteControl,
-    PlaylistModifyPrivate,
-    PlaylistModifyPublic,
-    PlaylistReadCollaborative,
-    PlaylistReadPrivate,
-    Streaming,
-    UgcImageUpload,
-    UserFollowRead,
-    UserLibraryModify,
-    UserLibraryRead,
-    UserFollowModify,
-    UserModifyPlaybackState,
-    UserReadCurrentlyPlaying,
-    UserReadEmail,
-    UserReadPlaybackState,
-    UserReadPlaybackPosition,
-    UserReadPrivate,
-    UserReadRecentlyPlayed,
-    UserTopRead,
+string_enum! {
+    /// Recommendation seed type: artist, track, genre. Spotify documents these in upper case,
+    /// but returns them in lower case.
+    pub enum RecommendationsSeedType {
+        Artist => "artist" | "ARTIST",
+        Track => "track" | "TRACK",
+        Genre => "genre" | "GENRE",
+    }
 }
 
-impl Scope {
-    fn as_str(&self) -> &'static str {
-        match self {
-            Scope::AppRemoteControl => "app-remote-control",
-            Scope::PlaylistModifyPrivate => "playlist-modify-private",
-            Scope::PlaylistModifyPublic => "playlist-modify-public",
-            Scope::PlaylistReadCollaborative => "playlist-read-collaborative",
-            Scope::PlaylistReadPrivate => "playlist-read-private",
-            Scope::Streaming => "streaming",
-            Scope::UgcImageUpload => "ugc-image-upload",
-            Scope::UserFollowRead => "user-follow-read",
-            Scope::UserLibraryModify => "user-library-modify",
-            Scope::UserLibraryRead => "user-library-read",
-            Scope::UserFollowModify => "user-follow-modify",
-            Scope::UserModifyPlaybackState => "user-modify-playback-state",
-            Scope::UserReadCurrentlyPlaying => "user-read-currently-playing",
-            Scope::UserReadEmail => "user-read-email",
-            Scope::UserReadPlaybackState => "user-read-playback-state",
-            Scope::UserReadPlaybackPosition => "user-read-playback-position",
-            Scope::UserReadPrivate => "user-read-private",
-            Scope::UserReadRecentlyPlayed => "user-read-recently-played",
-            Scope::UserTopRead => "user-top-read",
-        }
+string_enum! {
+    /// Device Type: computer, smartphone, speaker, TV, etc.
+    /// See the [Spotify developer
+    /// docs](https://developer.spotify.com/documentation/web-api/reference/player/get-a-users-available-devices/#device-types)
+    /// for more information.
+    ///
+    /// Spotify's own `Unknown` value is kept as an `Unknown` value.
+    pub enum DeviceType {
+        Computer => "Computer",
+        Tablet => "Tablet",
+        Smartphone => "Smartphone",
+        Speaker => "Speaker",
+        TV => "TV",
+        AVR => "AVR",
+        STB => "STB",
+        AudioDongle => "AudioDongle",
+        GameConsole => "GameConsole",
+        CastVideo => "CastVideo",
+        CastAudio => "CastAudio",
+        Automobile => "Automobile",
     }
 }
 
-impl AsRef<str> for Scope {
-    fn as_ref(&self) -> &'static str {
-        self.as_str()
+string_enum! {
+    /// Authorization scopes
+    pub enum Scope {
+        AppRemoteControl => "app-remote-control",
+        PlaylistModifyPrivate => "playlist-modify-private",
+        PlaylistModifyPublic => "playlist-modify-public",
+        PlaylistReadCollaborative => "playlist-read-collaborative",
+        PlaylistReadPrivate => "playlist-read-private",
+        Streaming => "streaming",
+        UgcImageUpload => "ugc-image-upload",
+        UserFollowRead => "user-follow-read",
+        UserLibraryModify => "user-library-modify",
+        UserLibraryRead => "user-library-read",
+        UserFollowModify => "user-follow-modify",
+        UserModifyPlaybackState => "user-modify-playback-state",
+        UserReadCurrentlyPlaying => "user-read-currently-playing",
+        UserReadEmail => "user-read-email",
+        UserReadPlaybackState => "user-read-playback-state",
+        UserReadPlaybackPosition => "user-read-playback-position",
+        UserReadPrivate => "user-read-private",
+        UserReadRecentlyPlayed => "user-read-recently-played",
+        UserTopRead => "user-top-read",
     }
 }
 
@@ -416,27 +310,23 @@ pub enum TrackAttribute {
 }
 
 impl TrackAttribute {
-    pub fn fmt_prefixed(&self, prefix: &str) -> String {
-        let param = match self {
-            TrackAttribute::Acousticness(v) => format!("acousticness={}", v),
-            TrackAttribute::Danceability(v) => format!("danceability={}", v),
-            TrackAttribute::DurationMs(v) => format!("duration_ms={}", v),
-            TrackAttribute::Energy(v) => format!("energy={}", v),
-            TrackAttribute::Instrumentalness(v) => format!("instrumentalness={}", v),
-            TrackAttribute::Key(v) => format!("key={}", v),
-            TrackAttribute::Liveness(v) => format!("liveness={}", v),
-            TrackAttribute::Loudness(v) => format!("loudness={}", v),
-            TrackAttribute::Mode(v) => format!("mode={}", v),
-            TrackAttribute::Popularity(v) => format!("popularity={}", v),
-            TrackAttribute::Speechiness(v) => format!("speechiness={}", v),
-            TrackAttribute::Tempo(v) => format!("tempo={}", v),
-            TrackAttribute::TimeSignature(v) => format!("time_signature={}", v),
-            TrackAttribute::Valence(v) => format!("valence={}", v),
-        };
-
-        // TODO: Is there a more efficient way to handle this?
-        let mut p = prefix.to_owned();
-        p.push_str(param.as_str());
-        p
+    /// The attribute's name, without a `min_`, `max_` or `target_` prefix, and its value.
+    pub fn name_and_value(&self) -> (&'static str, String) {
+        match self {
+            TrackAttribute::Acousticness(v) => ("acousticness", v.to_string()),
+            TrackAttribute::Danceability(v) => ("danceability", v.to_string()),
+            TrackAttribute::DurationMs(v) => ("duration_ms", v.to_string()),
+            TrackAttribute::Energy(v) => ("energy", v.to_string()),
+            TrackAttribute::Instrumentalness(v) => ("instrumentalness", v.to_string()),
+            TrackAttribute::Key(v) => ("key", v.to_string()),
+            TrackAttribute::Liveness(v) => ("liveness", v.to_string()),
+            TrackAttribute::Loudness(v) => ("loudness", v.to_string()),
+            TrackAttribute::Mode(v) => ("mode", v.to_string()),
+            TrackAttribute::Popularity(v) => ("popularity", v.to_string()),
+            TrackAttribute::Speechiness(v) => ("speechiness", v.to_string()),
+            TrackAttribute::Tempo(v) => ("tempo", v.to_string()),
+            TrackAttribute::TimeSignature(v) => ("time_signature", v.to_string()),
+            TrackAttribute::Valence(v) => ("valence", v.to_string()),
+        }
     }
 }
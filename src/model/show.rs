@@ -1,37 +1,50 @@
+use super::album::Restrictions;
+use super::common::Extra;
+use super::common::{Copyright, ExternalUrls};
+use super::id::{EpisodeId, ShowId, Uri};
 use super::image::Image;
 use super::page::Page;
-use std::collections::HashMap;
+use super::release_date::ReleaseDate;
+use super::senum::{MediaType, Type};
+use super::timestamp::Timestamp;
 
 /// Show object(simplified)
 /// [Show object simplified](https://developer.spotify.com/documentation/web-api/reference/object-model/#show-object-simplified)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimplifiedShow {
     pub available_markets: Vec<String>,
-    pub copyrights: Vec<HashMap<String, String>>,
+    pub copyrights: Vec<Copyright>,
     pub description: String,
+    pub html_description: String,
     pub explicit: bool,
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     pub href: String,
-    pub id: String,
+    pub id: ShowId,
     pub images: Vec<Image>,
     pub is_externally_hosted: Option<bool>,
     pub languages: Vec<String>,
-    pub media_type: String,
+    pub media_type: MediaType,
     pub name: String,
     pub publisher: String,
+    pub total_episodes: u32,
     #[serde(rename = "type")]
-    pub type_: String,
-    pub uri: String,
+    pub type_: Type,
+    pub uri: Uri<ShowId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SeversalSimplifiedShows {
-    pub shows: Vec<SimplifiedShow>,
+    /// `None` in the position of any ID that wasn't found
+    pub shows: Vec<Option<SimplifiedShow>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Show {
-    pub added_at: String,
+    pub added_at: Timestamp,
     pub show: SimplifiedShow,
 }
 
@@ -39,22 +52,26 @@ pub struct Show {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FullShow {
     pub available_markets: Vec<String>,
-    pub copyrights: Vec<HashMap<String, String>>,
+    pub copyrights: Vec<Copyright>,
     pub description: String,
+    pub html_description: String,
     pub explicit: bool,
     pub episodes: Page<SimplifiedEpisode>,
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     pub href: String,
-    pub id: String,
+    pub id: ShowId,
     pub images: Vec<Image>,
     pub is_externally_hosted: Option<bool>,
     pub languages: Vec<String>,
-    pub media_type: String,
+    pub media_type: MediaType,
     pub name: String,
     pub publisher: String,
+    pub total_episodes: u32,
     #[serde(rename = "type")]
-    pub type_: String,
-    pub uri: String,
+    pub type_: Type,
+    pub uri: Uri<ShowId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// [Episode object simplified](https://developer.spotify.com/documentation/web-api/reference/object-model/#episode-object-simplified)
@@ -62,11 +79,12 @@ pub struct FullShow {
 pub struct SimplifiedEpisode {
     pub audio_preview_url: Option<String>,
     pub description: String,
+    pub html_description: String,
     pub duration_ms: u32,
     pub explicit: bool,
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     pub href: String,
-    pub id: String,
+    pub id: EpisodeId,
     pub images: Vec<Image>,
     pub is_externally_hosted: bool,
     pub is_playable: bool,
@@ -74,12 +92,16 @@ pub struct SimplifiedEpisode {
     pub language: String,
     pub languages: Vec<String>,
     pub name: String,
-    pub release_date: String,
-    pub release_date_precision: String,
+    #[serde(flatten)]
+    pub release_date: ReleaseDate,
     pub resume_point: Option<ResumePoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
     #[serde(rename = "type")]
-    pub type_: String,
-    pub uri: String,
+    pub type_: Type,
+    pub uri: Uri<EpisodeId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// [Episode object full](https://developer.spotify.com/documentation/web-api/reference/object-model/#episode-object-full)
@@ -87,11 +109,12 @@ pub struct SimplifiedEpisode {
 pub struct FullEpisode {
     pub audio_preview_url: Option<String>,
     pub description: String,
+    pub html_description: String,
     pub duration_ms: u32,
     pub explicit: bool,
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     pub href: String,
-    pub id: String,
+    pub id: EpisodeId,
     pub images: Vec<Image>,
     pub is_externally_hosted: bool,
     pub is_playable: bool,
@@ -99,21 +122,36 @@ pub struct FullEpisode {
     pub language: String,
     pub languages: Vec<String>,
     pub name: String,
-    pub release_date: String,
-    pub release_date_precision: String,
+    #[serde(flatten)]
+    pub release_date: ReleaseDate,
     pub resume_point: Option<ResumePoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
     pub show: SimplifiedShow,
     #[serde(rename = "type")]
-    pub type_: String,
-    pub uri: String,
+    pub type_: Type,
+    pub uri: Uri<EpisodeId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullEpisodes {
-    pub episodes: Vec<FullEpisode>,
+    /// `None` in the position of any ID that wasn't found
+    pub episodes: Vec<Option<FullEpisode>>,
+}
+
+/// [Saved episode object](https://developer.spotify.com/documentation/web-api/reference/library/get-users-saved-episodes/)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SavedEpisode {
+    pub added_at: Timestamp,
+    pub episode: FullEpisode,
 }
 
 /// [](https://developer.spotify.com/documentation/web-api/reference/object-model/#resume-point-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ResumePoint {
     pub fully_played: bool,
     pub resume_position_ms: u32,
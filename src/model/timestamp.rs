@@ -1,4 +1,226 @@
-#[derive(Debug, Clone, Serialize, Deserialize)]
+//! Timestamps as returned by Spotify, like `added_at` and `played_at`
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{0:?} is not an RFC 3339 timestamp")]
+pub struct InvalidTimestamp(String);
+
+/// An instant in time, parsed from an RFC 3339 string like `2020-06-13T12:34:56Z`.
+///
+/// Timestamps serialize back to RFC 3339 in UTC, so any offset in the original is normalized.
+///
+/// ```rust
+/// use spotify_api::model::timestamp::Timestamp;
+///
+/// let earlier: Timestamp = "2020-06-13T14:34:56+02:00".parse().unwrap();
+/// let later: Timestamp = "2020-06-13T12:34:56.5Z".parse().unwrap();
+/// assert!(earlier < later);
+/// assert_eq!(earlier.to_string(), "2020-06-13T12:34:56Z");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct Timestamp {
-    inner: String,
+    inner: SystemTime,
+}
+
+impl Timestamp {
+    pub fn system_time(&self) -> SystemTime {
+        self.inner
+    }
+
+    // Seconds and nanoseconds relative to the epoch; seconds are negative for earlier instants
+    fn unix(&self) -> (i64, u32) {
+        match self.inner.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+            Err(e) => {
+                let d = e.duration();
+                let secs = -(d.as_secs() as i64);
+                match d.subsec_nanos() {
+                    0 => (secs, 0),
+                    n => (secs - 1, 1_000_000_000 - n),
+                }
+            }
+        }
+    }
+
+    fn from_unix(secs: i64, nanos: u32) -> Self {
+        let inner = if secs >= 0 {
+            UNIX_EPOCH + Duration::new(secs as u64, nanos)
+        } else {
+            UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs())
+                + Duration::from_nanos(nanos.into())
+        };
+        Timestamp { inner }
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(inner: SystemTime) -> Self {
+        Timestamp { inner }
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(t: Timestamp) -> Self {
+        t.inner
+    }
+}
+
+// Conversion between days since the epoch and proleptic Gregorian dates, following
+// http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let yoe = year - era * 400;
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = (if z >= 0 { z } else { z - 146_096 }) / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+pub(crate) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn number(s: &str) -> Option<u32> {
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn parse_rfc3339(s: &str) -> Option<Timestamp> {
+    // `YYYY-MM-DDTHH:MM:SS` is fixed-width; fractional seconds and the offset follow
+    if s.len() < 20 || !s.is_char_boundary(19) {
+        return None;
+    }
+    let (datetime, rest) = s.split_at(19);
+    let b = datetime.as_bytes();
+    if b[4] != b'-' || b[7] != b'-' || !matches!(b[10], b'T' | b't' | b' ') {
+        return None;
+    }
+    if b[13] != b':' || b[16] != b':' {
+        return None;
+    }
+
+    let year = i64::from(number(&datetime[0..4])?);
+    let month = number(&datetime[5..7])?;
+    let day = number(&datetime[8..10])?;
+    let hour = number(&datetime[11..13])?;
+    let minute = number(&datetime[14..16])?;
+    // Leap seconds are folded into the following second
+    let second = number(&datetime[17..19])?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let (nanos, offset) = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (fraction, offset) = rest.split_at(end);
+            if fraction.is_empty() {
+                return None;
+            }
+            // Anything past nanosecond precision is truncated
+            let digits = &fraction[..fraction.len().min(9)];
+            let nanos = number(digits)? * 10u32.pow(9 - digits.len() as u32);
+            (nanos, offset)
+        }
+        None => (0, rest),
+    };
+
+    let offset_secs = match offset {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match offset.as_bytes().first()? {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            if offset.len() != 6 || offset.as_bytes()[3] != b':' {
+                return None;
+            }
+            let hours = i64::from(number(&offset[1..3])?);
+            let minutes = i64::from(number(&offset[4..6])?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + i64::from(hour * 3600 + minute * 60 + second) - offset_secs;
+    Some(Timestamp::from_unix(secs, nanos))
+}
+
+impl FromStr for Timestamp {
+    type Err = InvalidTimestamp;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_rfc3339(s).ok_or_else(|| InvalidTimestamp(s.to_owned()))
+    }
+}
+
+impl TryFrom<String> for Timestamp {
+    type Error = InvalidTimestamp;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Timestamp> for String {
+    fn from(t: Timestamp) -> Self {
+        t.to_string()
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (secs, nanos) = self.unix();
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        let time = secs.rem_euclid(86_400);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        )?;
+        if nanos != 0 {
+            let fraction = format!("{:09}", nanos);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        f.write_str("Z")
+    }
 }
@@ -1,9 +1,11 @@
 //! All kinds of tracks object
-use std::collections::HashMap;
 
 use super::album::Restrictions;
 use super::album::SimplifiedAlbum;
 use super::artist::SimplifiedArtist;
+use super::common::Extra;
+use super::common::{ExternalIds, ExternalUrls};
+use super::id::{TrackId, Uri};
 use super::senum::Type;
 use super::timestamp::Timestamp;
 
@@ -17,10 +19,10 @@ pub struct FullTrack {
     pub disc_number: i32,
     pub duration_ms: u32,
     pub explicit: bool,
-    pub external_ids: HashMap<String, String>,
-    pub external_urls: HashMap<String, String>,
+    pub external_ids: ExternalIds,
+    pub external_urls: ExternalUrls,
     pub href: Option<String>,
-    pub id: Option<String>,
+    pub id: Option<TrackId>,
     pub is_local: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_playable: Option<bool>,
@@ -34,24 +36,38 @@ pub struct FullTrack {
     pub track_number: u32,
     #[serde(rename = "type")]
     pub type_: Type,
+    /// Local files have URIs like `spotify:local:{artist}:{album}:{title}:{seconds}`, so this
+    /// isn't a `Uri<TrackId>`
     pub uri: String,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 /// [link to track link] https://developer.spotify.com/documentation/web-api/reference/object-model/#track-link
 /// Track Link
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct TrackLink {
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     pub href: String,
-    pub id: String,
+    pub id: TrackId,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: String,
+    pub uri: Uri<TrackId>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullTracks {
+    /// A track that isn't found is `None` in the position of its ID
+    pub tracks: Vec<Option<FullTrack>>,
+}
+
+/// An artist's top tracks
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ArtistTopTracks {
     pub tracks: Vec<FullTrack>,
 }
 ///[track object simplified](https://developer.spotify.com/web-api/object-model/#track-object-simplified)
@@ -62,21 +78,32 @@ pub struct SimplifiedTrack {
     pub disc_number: i32,
     pub duration_ms: u32,
     pub explicit: bool,
-    pub external_urls: HashMap<String, String>,
+    pub external_urls: ExternalUrls,
     #[serde(default)]
     pub href: Option<String>,
-    pub id: Option<String>,
+    pub id: Option<TrackId>,
     pub is_local: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_playable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linked_from: Option<TrackLink>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
     pub name: String,
     pub preview_url: Option<String>,
     pub track_number: u32,
     #[serde(rename = "type")]
     pub type_: Type,
+    /// Local files have URIs like `spotify:local:{artist}:{album}:{title}:{seconds}`, so this
+    /// isn't a `Uri<TrackId>`
     pub uri: String,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 ///[saved track object](https://developer.spotify.com/web-api/object-model/#saved-track-object)
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SavedTrack {
     pub added_at: Timestamp,
     pub track: FullTrack,
@@ -1,22 +1,24 @@
 //! All kinds of user object
-use serde_json::Value;
-
-use std::collections::HashMap;
 
+use super::common::Extra;
+use super::common::{ExternalUrls, Followers};
+use super::id::{Uri, UserId};
 use super::image::Image;
-use super::senum::Type;
+use super::senum::{Product, Type};
 ///[public user object](https://developer.spotify.com/web-api/object-model/#user-object-public)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PublicUser {
     pub display_name: Option<String>,
-    pub external_urls: HashMap<String, String>,
-    pub followers: Option<HashMap<String, Option<Value>>>,
+    pub external_urls: ExternalUrls,
+    pub followers: Option<Followers>,
     pub href: String,
-    pub id: String,
+    pub id: UserId,
     pub images: Option<Vec<Image>>,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: String,
+    pub uri: Uri<UserId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
 }
 
 ///[private user object](https://developer.spotify.com/web-api/object-model/#user-object-private)
@@ -25,12 +27,32 @@ pub struct PrivateUser {
     pub country: Option<String>,
     pub display_name: Option<String>,
     pub email: Option<String>,
-    pub external_urls: HashMap<String, String>,
-    pub followers: Option<HashMap<String, Option<Value>>>,
+    pub explicit_content: Option<ExplicitContent>,
+    pub external_urls: ExternalUrls,
+    pub followers: Option<Followers>,
     pub href: String,
-    pub id: String,
+    pub id: UserId,
     pub images: Option<Vec<Image>>,
+    pub product: Option<Product>,
     #[serde(rename = "type")]
     pub type_: Type,
-    pub uri: String,
+    pub uri: Uri<UserId>,
+    #[serde(flatten, deserialize_with = "super::common::extra")]
+    pub extra: Extra,
+}
+
+///[explicit content settings](https://developer.spotify.com/documentation/web-api/reference/get-current-users-profile)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ExplicitContent {
+    pub filter_enabled: bool,
+    pub filter_locked: bool,
+}
+
+impl PrivateUser {
+    /// The user's country as an ISO 3166-1 alpha-2 code, suitable for use as the `market` of
+    /// other requests. Only available when the token was granted the `user-read-private` scope.
+    pub fn market(&self) -> Option<&str> {
+        self.country.as_deref()
+    }
 }
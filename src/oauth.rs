@@ -103,6 +103,7 @@ impl FileCache {
             .read(true)
             .write(true)
             .create(true)
+            .truncate(false)
             .open(&path)?;
 
         let mut token_bytes = Vec::new();
@@ -0,0 +1,150 @@
+//! Looking up audiobooks and chapters, against a client that serves the fixtures for a few IDs.
+#![cfg(feature = "api")]
+
+use http_types::StatusCode;
+use serde_json::{json, Value};
+
+use spotify_api::api::{audiobooks, chapters};
+use spotify_api::model::id::{AudiobookId, ChapterId};
+use spotify_api::Error as SpotifyError;
+
+mod common;
+use common::{error, fixture_with_id, id, ids, respond, Fixtures, Sent, Stub};
+
+/// How many audiobooks and chapters the catalog knows, with IDs made of 1 and up.
+const KNOWN: usize = 3;
+
+/// The fixture for an audiobook or chapter, with its ID replaced by `id` if the catalog knows it.
+fn item(fixture: &str, id: &str) -> Option<Value> {
+    if (1..=KNOWN).any(|n| common::id(n) == id) {
+        Some(fixture_with_id(fixture, id))
+    } else {
+        None
+    }
+}
+
+fn several(kind: &'static str, fixture: &'static str) -> impl Fn(&Sent) -> http_client::Response {
+    move |sent| {
+        let items: Vec<Option<Value>> = sent.ids().iter().map(|id| item(fixture, id)).collect();
+        respond(StatusCode::Ok, json!({ kind: items }))
+    }
+}
+
+fn one(fixture: &'static str) -> impl Fn(&Sent) -> http_client::Response {
+    move |sent| match item(fixture, &sent.last_segment()) {
+        Some(item) => respond(StatusCode::Ok, item),
+        None => error(StatusCode::NotFound, "Non existing id"),
+    }
+}
+
+/// Serves audiobook and chapter lookups for the known IDs.
+fn catalog() -> Stub<Fixtures> {
+    Stub(
+        Fixtures::new()
+            .route("/v1/audiobooks", several("audiobooks", "audiobook"))
+            .route("/v1/audiobooks/*", one("audiobook"))
+            .route("/v1/chapters", several("chapters", "chapter"))
+            .route("/v1/chapters/*", one("chapter")),
+    )
+}
+
+fn assert_not_found<T: std::fmt::Debug>(result: spotify_api::Result<T>) {
+    match result {
+        Err(SpotifyError::Api { status, .. }) => assert_eq!(status, StatusCode::NotFound),
+        other => panic!("expected 404, got {:?}", other),
+    }
+}
+
+#[async_std::test]
+async fn single_lookups_are_sent_by_id() {
+    let client = catalog();
+    let audiobook_id: AudiobookId = id(1).parse().unwrap();
+    let chapter_id: ChapterId = id(2).parse().unwrap();
+
+    let audiobook = audiobooks::audiobook_with_options(&client, &audiobook_id, Some("SE"))
+        .await
+        .unwrap();
+    let chapter = chapters::chapter(&client, &chapter_id).await.unwrap();
+
+    assert_eq!(audiobook.id, audiobook_id);
+    assert_eq!(chapter.id, chapter_id);
+    assert_eq!(
+        client.requests(),
+        vec![
+            format!("/v1/audiobooks/{}?market=SE", id(1)),
+            format!("/v1/chapters/{}", id(2)),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn single_lookups_of_unknown_ids_fail() {
+    let client = catalog();
+
+    assert_not_found(audiobooks::audiobook(&client, &id(KNOWN + 1).parse().unwrap()).await);
+    assert_not_found(chapters::chapter(&client, &id(KNOWN + 1).parse().unwrap()).await);
+}
+
+#[async_std::test]
+async fn several_lookups_have_none_for_unknown_ids() {
+    let client = catalog();
+    // Unknown IDs at the start, in the middle and at the end
+    let wanted: Vec<usize> = vec![9, 1, 8, 3, 7];
+    let audiobook_ids: Vec<AudiobookId> = wanted.iter().map(|&n| id(n).parse().unwrap()).collect();
+    let chapter_ids: Vec<ChapterId> = wanted.iter().map(|&n| id(n).parse().unwrap()).collect();
+
+    let found = audiobooks::audiobooks(&client, &audiobook_ids)
+        .await
+        .unwrap()
+        .audiobooks;
+    let found: Vec<Option<String>> = found
+        .into_iter()
+        .map(|a| a.map(|a| a.id.to_string()))
+        .collect();
+    assert_eq!(found, vec![None, Some(id(1)), None, Some(id(3)), None]);
+
+    let found = chapters::chapters_with_options(&client, &chapter_ids, Some("SE"))
+        .await
+        .unwrap()
+        .chapters;
+    let found: Vec<Option<String>> = found
+        .into_iter()
+        .map(|c| c.map(|c| c.id.to_string()))
+        .collect();
+    assert_eq!(found, vec![None, Some(id(1)), None, Some(id(3)), None]);
+
+    let joined = |ids: &[usize]| ids.iter().map(|&n| id(n)).collect::<Vec<_>>().join("%2C");
+    assert_eq!(
+        client.requests(),
+        vec![
+            format!("/v1/audiobooks?ids={}", joined(&wanted)),
+            format!("/v1/chapters?ids={}&market=SE", joined(&wanted)),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn several_lookups_are_split_50_at_a_time_in_order() {
+    let client = catalog();
+
+    let found = audiobooks::audiobooks(&client, &ids::<AudiobookId>(51))
+        .await
+        .unwrap()
+        .audiobooks;
+    assert_eq!(found.len(), 51);
+    assert_eq!(found.iter().filter(|a| a.is_some()).count(), KNOWN);
+    assert!(found[..KNOWN].iter().all(Option::is_some));
+
+    let found = chapters::chapters(&client, &ids::<ChapterId>(101))
+        .await
+        .unwrap()
+        .chapters;
+    assert_eq!(found.len(), 101);
+
+    let counts: Vec<usize> = client
+        .requests()
+        .iter()
+        .map(|r| r.split("ids=").nth(1).unwrap().split("%2C").count())
+        .collect();
+    assert_eq!(counts, vec![50, 1, 50, 50, 1]);
+}
@@ -0,0 +1,109 @@
+//! Batching against a client that answers from the fixtures.
+#![cfg(feature = "batch")]
+
+use futures::future::{join, join_all};
+use http_types::StatusCode;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use spotify_api::api::batch::Batcher;
+use spotify_api::model::id::ArtistId;
+
+mod common;
+use common::{error, fixture_with_id, respond, Fixtures, Stub};
+
+const UNKNOWN: &str = "0000000000000000000000";
+
+/// Answers `/artists?ids=` from the artist fixture with `null` for [`UNKNOWN`], or with an
+/// error if `status` isn't a success.
+fn artists(status: StatusCode) -> Stub<Fixtures> {
+    Stub(
+        Fixtures::new()
+            .route("/v1/artists", move |sent| {
+                if !status.is_success() {
+                    return error(status, "API rate limit exceeded");
+                }
+                let artists: Vec<Value> = sent
+                    .ids()
+                    .iter()
+                    .map(|id| match id.as_str() {
+                        UNKNOWN => Value::Null,
+                        id => fixture_with_id("artist", id),
+                    })
+                    .collect();
+                respond(StatusCode::Ok, json!({ "artists": artists }))
+            })
+            // Give concurrent callers a chance to find each request in flight
+            .delay(|_| Duration::from_millis(5)),
+    )
+}
+
+fn artist_id(n: usize) -> ArtistId {
+    format!("{:0>22}", n).parse().unwrap()
+}
+
+#[async_std::test]
+async fn lookups_in_one_window_share_a_request() {
+    let batcher = Batcher::new(artists(StatusCode::Ok));
+    let ids: Vec<ArtistId> = (1..=5).map(artist_id).collect();
+
+    let results = join_all(ids.iter().map(|id| batcher.artist(id))).await;
+
+    assert_eq!(batcher.client().requests().len(), 1);
+    for (id, result) in ids.iter().zip(results) {
+        assert_eq!(result.unwrap().unwrap().id, *id);
+    }
+}
+
+#[async_std::test]
+async fn full_batches_are_split_off() {
+    let batcher = Batcher::new(artists(StatusCode::Ok));
+    let ids: Vec<ArtistId> = (1..=120).map(artist_id).collect();
+
+    let results = join_all(ids.iter().map(|id| batcher.artist(id))).await;
+
+    let requests = batcher.client().requests();
+    let sizes: Vec<usize> = requests
+        .iter()
+        .map(|r| r.matches("%2C").count() + 1)
+        .collect();
+    assert_eq!(sizes, vec![50, 50, 20]);
+    for (id, result) in ids.iter().zip(results) {
+        assert_eq!(result.unwrap().unwrap().id, *id);
+    }
+}
+
+#[async_std::test]
+async fn unknown_and_repeated_ids() {
+    let batcher = Batcher::new(artists(StatusCode::Ok));
+    let known = artist_id(7);
+    let unknown: ArtistId = UNKNOWN.parse().unwrap();
+
+    let (a, b) = join(batcher.artist(&known), batcher.artist(&known)).await;
+    let missing = batcher.artist(&unknown).await.unwrap();
+
+    assert_eq!(a.unwrap().unwrap().id, known);
+    assert_eq!(b.unwrap().unwrap().id, known);
+    assert!(missing.is_none());
+    let requests = batcher.client().requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].ends_with(&format!("ids={}", known)));
+}
+
+#[async_std::test]
+async fn errors_reach_every_caller() {
+    let batcher = Batcher::new(artists(StatusCode::TooManyRequests));
+    let ids: Vec<ArtistId> = (1..=3).map(artist_id).collect();
+
+    let results = join_all(ids.iter().map(|id| batcher.artist(id))).await;
+
+    assert_eq!(batcher.client().requests().len(), 1);
+    for result in results {
+        match result {
+            Err(spotify_api::Error::Api { status, .. }) => {
+                assert_eq!(status, StatusCode::TooManyRequests)
+            }
+            other => panic!("expected a rate limit error, got {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,397 @@
+//! Response caching against a client that answers like Spotify's catalog and playlist endpoints.
+#![cfg(feature = "api")]
+
+use http_client::{Request, Response};
+use http_types::headers::{CACHE_CONTROL, ETAG, VARY};
+use http_types::{Method, StatusCode, Url};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use spotify_api::api::cache::{CachingSpotifyClient, FileResponseCache, MemoryResponseCache};
+use spotify_api::api::{albums, follow, player, playlists, SpotifyClient};
+use spotify_api::clock::Clock;
+use spotify_api::model::id::{AlbumId, PlaylistId, UserId};
+
+mod common;
+use common::{Fixtures, Sent, Stub};
+
+const ALBUM: &str = include_str!("fixtures/album.json");
+const PLAYLIST: &str = include_str!("fixtures/playlist.json");
+const PLAYLIST_ITEMS: &str = include_str!("fixtures/playlist_items.json");
+const ETAG_VALUE: &str = "\"MC-ZmM0ZjNjNjg=\"";
+
+/// Clones share the same time.
+#[derive(Clone, Debug, Default)]
+struct ManualClock {
+    secs: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    fn advance(&self, secs: u64) {
+        self.secs.fetch_add(secs, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_600_000_000 + self.secs.load(Ordering::SeqCst))
+    }
+}
+
+/// Serves playlists and their items with their fixtures and everything else with the album
+/// fixture, along with an ETag, `cache_control` and `vary`. Answers `304` when the ETag matches.
+fn catalog(cache_control: &'static str, vary: Option<&'static str>) -> Stub<Fixtures> {
+    let answer = move |body: &'static str| {
+        move |sent: &Sent| {
+            let mut resp = if sent.header("If-None-Match") == Some(ETAG_VALUE) {
+                Response::new(StatusCode::NotModified)
+            } else {
+                let mut resp = Response::new(StatusCode::Ok);
+                resp.set_body(body);
+                resp.insert_header("Content-Type", "application/json; charset=utf-8");
+                resp
+            };
+            resp.insert_header(ETAG, ETAG_VALUE);
+            resp.insert_header(CACHE_CONTROL, cache_control);
+            if let Some(vary) = vary {
+                resp.insert_header(VARY, vary);
+            }
+            resp
+        }
+    };
+    Stub(
+        Fixtures::new()
+            .route("/v1/playlists/*", answer(PLAYLIST))
+            .route("/v1/playlists/*/tracks", answer(PLAYLIST_ITEMS))
+            .otherwise(answer(ALBUM)),
+    )
+}
+
+/// Path and `If-None-Match` of every request.
+fn requests(catalog: &Fixtures) -> Vec<(String, Option<String>)> {
+    catalog
+        .sent()
+        .iter()
+        .map(|sent| {
+            let etag = sent.header("If-None-Match").map(str::to_owned);
+            (sent.url.path().to_owned(), etag)
+        })
+        .collect()
+}
+
+fn album_id() -> AlbumId {
+    "4MnIC5UuY8BzHI0yvFU5m3".parse().unwrap()
+}
+
+fn playlist_id() -> PlaylistId {
+    "3cEYpjA9oz9GiPac4AsH4n".parse().unwrap()
+}
+
+const SCOPE: &str = "smedjan";
+
+#[async_std::test]
+async fn fresh_responses_are_served_without_a_request() {
+    let client = CachingSpotifyClient::with_clock(
+        catalog("public, max-age=60", None),
+        MemoryResponseCache::new(),
+        SCOPE,
+        ManualClock::default(),
+    );
+
+    let first = albums::album(&client, &album_id()).await.unwrap();
+    let second = albums::album(&client, &album_id()).await.unwrap();
+
+    assert_eq!(first.id, second.id);
+    assert_eq!(second.name, "Everything All The Time");
+    assert_eq!(requests(&client.into_inner()).len(), 1);
+}
+
+#[async_std::test]
+async fn stale_responses_are_revalidated() {
+    let clock = ManualClock::default();
+    let client = CachingSpotifyClient::with_clock(
+        catalog("public, max-age=60", None),
+        MemoryResponseCache::new(),
+        SCOPE,
+        clock.clone(),
+    );
+
+    albums::album(&client, &album_id()).await.unwrap();
+    clock.advance(61);
+    let album = albums::album(&client, &album_id()).await.unwrap();
+    assert_eq!(album.id, album_id());
+    // The 304 renewed the max-age, so this one doesn't need a request at all
+    clock.advance(30);
+    albums::album(&client, &album_id()).await.unwrap();
+
+    let requests = requests(&client.into_inner());
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].1, None);
+    assert_eq!(requests[1].1.as_deref(), Some(ETAG_VALUE));
+}
+
+#[async_std::test]
+async fn no_cache_always_revalidates() {
+    let client =
+        CachingSpotifyClient::new(catalog("no-cache", None), MemoryResponseCache::new(), SCOPE);
+
+    for _ in 0..3 {
+        albums::album(&client, &album_id()).await.unwrap();
+    }
+
+    let requests = requests(&client.into_inner());
+    let revalidated: Vec<_> = requests.iter().map(|(_, etag)| etag.is_some()).collect();
+    assert_eq!(revalidated, vec![false, true, true]);
+}
+
+#[async_std::test]
+async fn no_store_is_not_cached() {
+    let client = CachingSpotifyClient::new(
+        catalog("no-store, max-age=60", None),
+        MemoryResponseCache::new(),
+        SCOPE,
+    );
+
+    albums::album(&client, &album_id()).await.unwrap();
+    albums::album(&client, &album_id()).await.unwrap();
+
+    let requests = requests(&client.into_inner());
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().all(|(_, etag)| etag.is_none()));
+}
+
+#[async_std::test]
+async fn player_is_never_cached() {
+    let client = CachingSpotifyClient::new(
+        catalog("public, max-age=60", None),
+        MemoryResponseCache::new(),
+        SCOPE,
+    );
+
+    // The album fixture isn't a queue, so only the requests matter here
+    let _ = player::queue(&client).await;
+    let _ = player::queue(&client).await;
+
+    let requests = requests(&client.into_inner());
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|(path, etag)| path == "/v1/me/player/queue" && etag.is_none()));
+}
+
+#[async_std::test]
+async fn only_catalog_and_playlist_lookups_are_cached() {
+    let client = CachingSpotifyClient::new(
+        catalog("public, max-age=60", None),
+        MemoryResponseCache::new(),
+        SCOPE,
+    );
+    let users: [UserId; 1] = ["smedjan".parse().unwrap()];
+
+    for _ in 0..2 {
+        playlists::playlist(&client, &playlist_id()).await.unwrap();
+        playlists::playlist_items(&client, &playlist_id())
+            .await
+            .unwrap();
+        // The fixtures are none of these, so only the requests matter here
+        let _ = follow::users_follow_playlist(&client, &playlist_id(), &users).await;
+        let _ = playlists::user_playlists(&client, &users[0]).await;
+    }
+
+    let paths: Vec<String> = requests(&client.into_inner())
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    let uncached = vec![
+        "/v1/playlists/3cEYpjA9oz9GiPac4AsH4n/followers/contains",
+        "/v1/users/smedjan/playlists",
+    ];
+    let expected = [
+        vec![
+            "/v1/playlists/3cEYpjA9oz9GiPac4AsH4n",
+            "/v1/playlists/3cEYpjA9oz9GiPac4AsH4n/tracks",
+        ],
+        uncached.clone(),
+        uncached,
+    ]
+    .concat();
+    assert_eq!(paths, expected);
+}
+
+#[async_std::test]
+async fn playlists_are_revalidated_and_served_on_not_modified() {
+    // What Spotify sends with playlists
+    let client = CachingSpotifyClient::new(
+        catalog("private, max-age=0", Some("Authorization")),
+        MemoryResponseCache::new(),
+        SCOPE,
+    );
+
+    let first = playlists::playlist(&client, &playlist_id()).await.unwrap();
+    let second = playlists::playlist(&client, &playlist_id()).await.unwrap();
+    let items = playlists::playlist_items(&client, &playlist_id())
+        .await
+        .unwrap();
+    let items_again = playlists::playlist_items(&client, &playlist_id())
+        .await
+        .unwrap();
+
+    assert_eq!(second.id, first.id);
+    assert_eq!(second.snapshot_id, first.snapshot_id);
+    assert_eq!(items_again.items.len(), items.items.len());
+    let requests = requests(&client.into_inner());
+    let revalidated: Vec<bool> = requests.iter().map(|(_, etag)| etag.is_some()).collect();
+    assert_eq!(revalidated, vec![false, true, false, true]);
+    assert!(requests
+        .iter()
+        .filter_map(|(_, etag)| etag.as_deref())
+        .all(|etag| etag == ETAG_VALUE));
+}
+
+#[async_std::test]
+async fn scopes_sharing_a_cache_never_share_entries() {
+    // `Cache-Control`, `Vary`, and how many requests a client in the same scope sends after the
+    // first one
+    let cases = [
+        ("public, max-age=60", None, 0),
+        ("private, max-age=60", None, 0),
+        ("max-age=60", Some("Authorization"), 0),
+        ("max-age=60", Some("Accept-Encoding"), 0),
+        ("max-age=60", Some("*"), 1),
+    ];
+
+    for (i, &(cache_control, vary, same_scope)) in cases.iter().enumerate() {
+        // One cache directory shared by every client
+        let dir =
+            std::env::temp_dir().join(format!("spotify-api-scopes-{}-{}", i, std::process::id()));
+        let cache = || FileResponseCache::new(dir.clone()).unwrap();
+        let alice = CachingSpotifyClient::with_clock(
+            catalog(cache_control, vary),
+            cache(),
+            "alice",
+            ManualClock::default(),
+        );
+        let alice_again = CachingSpotifyClient::with_clock(
+            catalog(cache_control, vary),
+            cache(),
+            "alice",
+            ManualClock::default(),
+        );
+        let bob = CachingSpotifyClient::with_clock(
+            catalog(cache_control, vary),
+            cache(),
+            "bob",
+            ManualClock::default(),
+        );
+
+        albums::album(&alice, &album_id()).await.unwrap();
+        albums::album(&alice_again, &album_id()).await.unwrap();
+        albums::album(&bob, &album_id()).await.unwrap();
+
+        let context = format!("{} {:?}", cache_control, vary);
+        assert_eq!(
+            requests(&alice_again.into_inner()).len(),
+            same_scope,
+            "{}",
+            context
+        );
+        // Nothing from Alice was kept for Bob to revalidate either
+        assert_eq!(
+            requests(&bob.into_inner()),
+            vec![(format!("/v1/albums/{}", album_id()), None)],
+            "{}",
+            context
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
+
+#[async_std::test]
+async fn responses_are_only_served_for_the_headers_they_vary_by() {
+    let client = CachingSpotifyClient::new(
+        catalog("max-age=60", Some("Accept-Language")),
+        MemoryResponseCache::new(),
+        SCOPE,
+    );
+    let url = Url::parse(&format!("https://api.spotify.com/v1/albums/{}", album_id())).unwrap();
+    let request = |language: Option<&str>| {
+        let mut req = Request::new(Method::Get, url.clone());
+        if let Some(language) = language {
+            req.insert_header("Accept-Language", language);
+        }
+        req
+    };
+
+    for language in &[Some("sv"), Some("sv"), Some("en"), None, Some("sv")] {
+        let resp = client.send_authorized(request(*language)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::Ok);
+    }
+
+    // Only the second Swedish request could be served from the cache. The English one replaced
+    // the Swedish entry, which the request without a language then replaced in turn.
+    assert_eq!(requests(&client.into_inner()).len(), 4);
+}
+
+#[async_std::test]
+async fn file_cache_outlives_the_client() {
+    let dir = std::env::temp_dir().join(format!("spotify-api-cache-{}", std::process::id()));
+    let client = CachingSpotifyClient::with_clock(
+        catalog("public, max-age=60", None),
+        FileResponseCache::new(dir.clone()).unwrap(),
+        SCOPE,
+        ManualClock::default(),
+    );
+    albums::album(&client, &album_id()).await.unwrap();
+    assert_eq!(requests(&client.into_inner()).len(), 1);
+
+    let client = CachingSpotifyClient::with_clock(
+        catalog("public, max-age=60", None),
+        FileResponseCache::new(dir.clone()).unwrap(),
+        SCOPE,
+        ManualClock::default(),
+    );
+    let album = albums::album(&client, &album_id()).await.unwrap();
+    assert_eq!(album.id, album_id());
+    assert_eq!(requests(&client.into_inner()).len(), 0);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[async_std::test]
+async fn damaged_file_entries_are_misses() {
+    let dir = std::env::temp_dir().join(format!("spotify-api-damaged-{}", std::process::id()));
+    let client = CachingSpotifyClient::with_clock(
+        catalog("public, max-age=60", None),
+        FileResponseCache::new(dir.clone()).unwrap(),
+        SCOPE,
+        ManualClock::default(),
+    );
+    albums::album(&client, &album_id()).await.unwrap();
+    let entry = std::fs::read_dir(&dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+
+    let mut stored: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&entry).unwrap()).unwrap();
+    stored["status"] = serde_json::json!(999);
+    let damaged = [serde_json::to_vec(&stored).unwrap(), b"{\"url\":".to_vec()];
+
+    for contents in &damaged {
+        std::fs::write(&entry, contents).unwrap();
+        let client = CachingSpotifyClient::with_clock(
+            catalog("public, max-age=60", None),
+            FileResponseCache::new(dir.clone()).unwrap(),
+            SCOPE,
+            ManualClock::default(),
+        );
+        let album = albums::album(&client, &album_id()).await.unwrap();
+        assert_eq!(album.id, album_id());
+        assert_eq!(requests(&client.into_inner()).len(), 1);
+    }
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
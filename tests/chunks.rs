@@ -0,0 +1,95 @@
+//! How the chunks of a request for more IDs than Spotify takes at once are sent, against a
+//! client that answers earlier requests more slowly.
+#![cfg(feature = "api")]
+
+use http_client::Response;
+use http_types::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+
+use spotify_api::api::library;
+use spotify_api::model::id::TrackId;
+
+mod common;
+use common::{error, ids, respond, Event, Fixtures, Sent, Stub};
+
+/// Answers `contains` requests with whether each ID is an even number, and other requests with
+/// an empty success. Earlier requests are answered more slowly, and request number `fail_at`
+/// fails.
+fn slow_library(fail_at: Option<usize>) -> Stub<Fixtures> {
+    let answer = move |sent: &Sent, contains: bool| {
+        if Some(sent.n) == fail_at {
+            error(StatusCode::InternalServerError, "Server error")
+        } else if contains {
+            let even: Vec<bool> = sent
+                .ids()
+                .iter()
+                .map(|id| id.parse::<usize>().unwrap() % 2 == 0)
+                .collect();
+            respond(StatusCode::Ok, json!(even))
+        } else {
+            Response::new(StatusCode::Ok)
+        }
+    };
+    Stub(
+        Fixtures::new()
+            .route("/v1/me/tracks/contains", move |sent| answer(sent, true))
+            .route("/v1/me/tracks", move |sent| answer(sent, false))
+            .delay(|n| Duration::from_millis(5 * 10u64.saturating_sub(n as u64))),
+    )
+}
+
+#[async_std::test]
+async fn reads_are_sent_together_and_keep_the_order_of_ids() {
+    let client = slow_library(None);
+    let contains = library::library_contains_tracks(&client, &ids::<TrackId>(200))
+        .await
+        .unwrap();
+
+    let expected: Vec<bool> = (1..=200).map(|i| i % 2 == 0).collect();
+    assert_eq!(contains, expected);
+
+    // The chunks were in flight together, and the later ones were answered first
+    let events = client.events();
+    assert_eq!(events[..2], [Event::Sent(0), Event::Sent(1)]);
+    let answered: Vec<&Event> = events
+        .iter()
+        .filter(|e| matches!(e, Event::Answered(_)))
+        .collect();
+    assert_ne!(*answered[0], Event::Answered(0));
+}
+
+#[async_std::test]
+async fn mutations_are_sent_in_turn() {
+    let client = slow_library(None);
+    library::library_save_tracks(&client, &ids::<TrackId>(60))
+        .await
+        .unwrap();
+
+    assert_eq!(client.id_counts(), vec![50, 10]);
+    // Each chunk is only sent once the one before it has been answered
+    assert_eq!(
+        client.events(),
+        vec![
+            Event::Sent(0),
+            Event::Answered(0),
+            Event::Sent(1),
+            Event::Answered(1)
+        ]
+    );
+}
+
+#[async_std::test]
+async fn mutations_stop_at_the_first_error() {
+    let client = slow_library(Some(1));
+    let result = library::library_save_tracks(&client, &ids::<TrackId>(200)).await;
+
+    match result {
+        Err(spotify_api::Error::Api { status, .. }) => {
+            assert_eq!(status, StatusCode::InternalServerError)
+        }
+        other => panic!("expected an API error, got {:?}", other),
+    }
+    // The chunks after the failed one are never sent
+    assert_eq!(client.id_counts(), vec![50, 50]);
+}
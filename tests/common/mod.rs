@@ -0,0 +1,362 @@
+//! Helpers shared by the integration tests: a client whose answers are up to each test, a
+//! responder that serves the fixtures and records what it was sent, and a seeded `FakeSpotify`.
+// Each test file uses some of these
+#![allow(dead_code, unused_imports)]
+
+use futures::future::BoxFuture;
+use http_client::{Error, HttpClient, Request, Response};
+use http_types::{Method, StatusCode, Url};
+use serde_json::{json, Value};
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use spotify_api::api::SpotifyClient;
+
+/// Answers the requests sent through a [`Stub`].
+pub trait Answer: Debug + Send + Sync + Unpin + 'static {
+    fn answer(&self, req: Request) -> BoxFuture<'static, Result<Response, Error>>;
+}
+
+/// A client whose authorized requests are answered by `A`. Tests only send authorized
+/// requests, so anything else is a bug in the test.
+#[derive(Debug, Default)]
+pub struct Stub<A>(pub A);
+
+impl<A> Deref for Stub<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        &self.0
+    }
+}
+
+impl<A: Answer> HttpClient for Stub<A> {
+    fn send(&self, _req: Request) -> BoxFuture<'static, Result<Response, Error>> {
+        unimplemented!("only authorized requests are expected")
+    }
+}
+
+impl<A: Answer> SpotifyClient for Stub<A> {
+    fn send_authorized(&self, req: Request) -> BoxFuture<'static, Result<Response, Error>> {
+        self.0.answer(req)
+    }
+}
+
+/// A fixture as JSON.
+pub fn fixture_value(name: &str) -> Value {
+    let path = format!(
+        "{}/tests/fixtures/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// A fixture with its ID replaced by `id`.
+pub fn fixture_with_id(name: &str, id: &str) -> Value {
+    let mut value = fixture_value(name);
+    value["id"] = json!(id);
+    value
+}
+
+/// A JSON response.
+pub fn respond(status: StatusCode, body: impl Into<http_types::Body>) -> Response {
+    let mut resp = Response::new(status);
+    resp.set_body(body);
+    resp.insert_header("Content-Type", "application/json");
+    resp
+}
+
+/// A response with the error object Spotify sends along with `status`.
+pub fn error(status: StatusCode, message: &str) -> Response {
+    let error = json!({ "status": status as u16, "message": message });
+    respond(status, json!({ "error": error }))
+}
+
+/// A request received by [`Fixtures`], numbered from 0 in the order they were received.
+#[derive(Clone, Debug)]
+pub struct Sent {
+    pub n: usize,
+    pub method: Method,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Sent {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn param(&self, name: &str) -> Option<String> {
+        self.url
+            .query_pairs()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.into_owned())
+    }
+
+    /// The IDs in the `ids` parameter.
+    pub fn ids(&self) -> Vec<String> {
+        match self.param("ids") {
+            Some(ids) => ids.split(',').map(str::to_owned).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The last segment of the path.
+    pub fn last_segment(&self) -> String {
+        self.url
+            .path_segments()
+            .unwrap()
+            .next_back()
+            .unwrap()
+            .to_owned()
+    }
+
+    pub fn path_and_query(&self) -> String {
+        match self.url.query() {
+            Some(query) => format!("{}?{}", self.url.path(), query),
+            None => self.url.path().to_owned(),
+        }
+    }
+}
+
+/// Something that happened to a request sent to [`Fixtures`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    Sent(usize),
+    Answered(usize),
+}
+
+type Handler = Arc<dyn Fn(&Sent) -> Response + Send + Sync>;
+
+#[derive(Debug, Default)]
+struct Log {
+    sent: Vec<Sent>,
+    events: Vec<Event>,
+}
+
+/// Answers requests by path, and keeps every request it was sent. Clones share their routes and
+/// what they were sent, so a test can keep one to look at while a client owns another.
+///
+/// Paths are matched segment by segment, where `*` matches any one segment. A request that no
+/// route matches fails the test, unless there's a route for everything else.
+#[derive(Clone, Default)]
+pub struct Fixtures {
+    routes: Vec<(String, Handler)>,
+    otherwise: Option<Handler>,
+    delay: Option<Arc<dyn Fn(usize) -> Duration + Send + Sync>>,
+    log: Arc<Mutex<Log>>,
+}
+
+impl Debug for Fixtures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let routes: Vec<&str> = self.routes.iter().map(|(path, _)| path.as_str()).collect();
+        f.debug_struct("Fixtures").field("routes", &routes).finish()
+    }
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    pattern.len() == path.len() && pattern.iter().zip(&path).all(|(p, s)| *p == "*" || p == s)
+}
+
+impl Fixtures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer requests for `path` with `handler`.
+    pub fn route(
+        mut self,
+        path: &str,
+        handler: impl Fn(&Sent) -> Response + Send + Sync + 'static,
+    ) -> Self {
+        self.routes.push((path.to_owned(), Arc::new(handler)));
+        self
+    }
+
+    /// Answer requests for `path` with a fixture.
+    pub fn fixture(self, path: &str, name: &str) -> Self {
+        let body = fixture_value(name);
+        self.route(path, move |_| respond(StatusCode::Ok, body.clone()))
+    }
+
+    /// Answer requests that no route matches with `handler`.
+    pub fn otherwise(
+        mut self,
+        handler: impl Fn(&Sent) -> Response + Send + Sync + 'static,
+    ) -> Self {
+        self.otherwise = Some(Arc::new(handler));
+        self
+    }
+
+    /// Wait before answering request number `n` for as long as `delay(n)`.
+    pub fn delay(mut self, delay: impl Fn(usize) -> Duration + Send + Sync + 'static) -> Self {
+        self.delay = Some(Arc::new(delay));
+        self
+    }
+
+    pub fn sent(&self) -> Vec<Sent> {
+        self.log.lock().unwrap().sent.clone()
+    }
+
+    /// The path and query of every request.
+    pub fn requests(&self) -> Vec<String> {
+        self.sent().iter().map(Sent::path_and_query).collect()
+    }
+
+    /// The number of IDs in the `ids` parameter of every request.
+    pub fn id_counts(&self) -> Vec<usize> {
+        self.sent().iter().map(|sent| sent.ids().len()).collect()
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.log.lock().unwrap().events.clone()
+    }
+
+    fn handler(&self, path: &str) -> Handler {
+        let route = self.routes.iter().find(|(p, _)| path_matches(p, path));
+        match (route, &self.otherwise) {
+            (Some((_, handler)), _) | (None, Some(handler)) => Arc::clone(handler),
+            (None, None) => panic!("unexpected request for {}", path),
+        }
+    }
+}
+
+impl Answer for Fixtures {
+    fn answer(&self, mut req: Request) -> BoxFuture<'static, Result<Response, Error>> {
+        let fixtures = self.clone();
+        Box::pin(async move {
+            let mut sent = Sent {
+                n: 0,
+                method: req.method(),
+                url: req.url().clone(),
+                headers: req
+                    .iter()
+                    .flat_map(|(name, values)| {
+                        values
+                            .iter()
+                            .map(move |v| (name.as_str().to_owned(), v.as_str().to_owned()))
+                    })
+                    .collect(),
+                body: req.body_string().await?,
+            };
+            {
+                let mut log = fixtures.log.lock().unwrap();
+                sent.n = log.sent.len();
+                log.sent.push(sent.clone());
+                log.events.push(Event::Sent(sent.n));
+            }
+
+            let resp = fixtures.handler(sent.url.path())(&sent);
+            if let Some(delay) = &fixtures.delay {
+                async_std::task::sleep(delay(sent.n)).await;
+            }
+            let mut log = fixtures.log.lock().unwrap();
+            log.events.push(Event::Answered(sent.n));
+            Ok(resp)
+        })
+    }
+}
+
+/// An ID made of `n`, padded to the length of a Spotify ID.
+pub fn id(n: usize) -> String {
+    format!("{:0>22}", n)
+}
+
+/// The IDs made of 1 to `n`.
+pub fn ids<I: std::str::FromStr>(n: usize) -> Vec<I>
+where
+    I::Err: Debug,
+{
+    (1..=n).map(|n| id(n).parse().unwrap()).collect()
+}
+
+#[cfg(feature = "testing")]
+pub use fake::*;
+
+#[cfg(feature = "testing")]
+mod fake {
+    use http_types::StatusCode;
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+
+    use spotify_api::api::fake::FakeSpotify;
+    use spotify_api::model::artist::FullArtist;
+    use spotify_api::model::id::{TrackId, UserId};
+    use spotify_api::model::track::FullTrack;
+    use spotify_api::Error;
+
+    use super::id;
+
+    /// A fixture with its ID and URI replaced by `id`.
+    pub fn fixture<T: DeserializeOwned>(name: &str, id: &str) -> T {
+        let mut value = super::fixture_with_id(name, id);
+        let kind = value["type"].as_str().unwrap().to_owned();
+        value["uri"] = json!(format!("spotify:{}:{}", kind, id));
+        serde_json::from_value(value).unwrap()
+    }
+
+    pub fn track(n: usize) -> FullTrack {
+        fixture("track", &id(n))
+    }
+
+    pub fn track_id(n: usize) -> TrackId {
+        id(n).parse().unwrap()
+    }
+
+    pub fn track_uri(n: usize) -> String {
+        format!("spotify:track:{}", id(n))
+    }
+
+    pub fn me() -> UserId {
+        "smedjan".parse().unwrap()
+    }
+
+    /// A fake with tracks 1 to 10 and artists 1 to 5 in its catalog.
+    pub fn spotify() -> FakeSpotify {
+        let spotify = FakeSpotify::new(fixture("user", "smedjan"));
+        for n in 1..=10 {
+            spotify.add_track(track(n));
+        }
+        for n in 1..=5 {
+            spotify.add_artist(fixture::<FullArtist>("artist", &id(n)));
+        }
+        spotify
+    }
+
+    /// A fake with tracks 1 to `n` and artists 1 to 5 in its catalog.
+    pub fn spotify_with_tracks(n: usize) -> FakeSpotify {
+        let spotify = spotify();
+        for n in 11..=n {
+            spotify.add_track(track(n));
+        }
+        spotify
+    }
+
+    pub fn assert_status<T: std::fmt::Debug>(result: spotify_api::Result<T>, expected: StatusCode) {
+        match result {
+            Err(Error::Api { status, .. }) => assert_eq!(status, expected),
+            other => panic!("expected {}, got {:?}", expected, other),
+        }
+    }
+
+    /// The number of IDs in each request the fake received, in order.
+    pub fn id_counts(spotify: &FakeSpotify) -> Vec<usize> {
+        spotify
+            .requests()
+            .iter()
+            .map(|r| match r.split("ids=").nth(1) {
+                Some(ids) => ids.split('&').next().unwrap().split("%2C").count(),
+                None => 0,
+            })
+            .collect()
+    }
+}
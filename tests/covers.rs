@@ -0,0 +1,83 @@
+//! Uploading playlist cover images, against a client that records what it's sent.
+#![cfg(feature = "api")]
+
+use http_client::Response;
+use http_types::{Method, StatusCode};
+
+use spotify_api::api::playlists;
+use spotify_api::model::id::PlaylistId;
+use spotify_api::Error as SpotifyError;
+
+mod common;
+use common::{Fixtures, Stub};
+
+/// Accepts every upload like Spotify does, and keeps what was sent.
+fn recorder() -> Stub<Fixtures> {
+    Stub(Fixtures::new().route("/v1/playlists/*/images", |_| {
+        Response::new(StatusCode::Accepted)
+    }))
+}
+
+fn playlist_id() -> PlaylistId {
+    "3cEYpjA9oz9GiPac4AsH4n".parse().unwrap()
+}
+
+/// A JPEG of `len` bytes, as far as its first bytes go.
+fn jpeg(len: usize) -> Vec<u8> {
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0];
+    jpeg.resize(len, 0x42);
+    jpeg
+}
+
+#[async_std::test]
+async fn jpegs_are_sent_base64_encoded_as_jpeg() {
+    let client = recorder();
+    let image = jpeg(64);
+
+    playlists::upload_playlist_cover_image(&client, &playlist_id(), &image)
+        .await
+        .unwrap();
+
+    let sent = client.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].method, Method::Put);
+    assert_eq!(
+        sent[0].path_and_query(),
+        "/v1/playlists/3cEYpjA9oz9GiPac4AsH4n/images"
+    );
+    assert_eq!(sent[0].header("Content-Type"), Some("image/jpeg"));
+    assert_eq!(sent[0].body, base64::encode(&image));
+}
+
+#[async_std::test]
+async fn other_images_are_rejected_without_a_request() {
+    let client = recorder();
+    let png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    for image in &[png, Vec::new(), vec![0xFF, 0xD8]] {
+        let result = playlists::upload_playlist_cover_image(&client, &playlist_id(), image).await;
+        assert!(matches!(result, Err(SpotifyError::NotJpeg)), "{:?}", result);
+    }
+    assert!(client.sent().is_empty());
+}
+
+#[async_std::test]
+async fn the_size_limit_applies_to_the_encoded_image() {
+    let client = recorder();
+
+    // Every 3 bytes take 4 once encoded, so this is exactly 256 KB
+    let largest = jpeg(256 * 1024 / 4 * 3);
+    playlists::upload_playlist_cover_image(&client, &playlist_id(), &largest)
+        .await
+        .unwrap();
+    assert_eq!(client.sent()[0].body.len(), 256 * 1024);
+
+    let result =
+        playlists::upload_playlist_cover_image(&client, &playlist_id(), &jpeg(largest.len() + 1))
+            .await;
+    match result {
+        Err(SpotifyError::ImageTooLarge(size)) => assert_eq!(size, 256 * 1024 + 4),
+        other => panic!("expected the image to be too large, got {:?}", other),
+    }
+    assert_eq!(client.sent().len(), 1);
+}
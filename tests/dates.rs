@@ -0,0 +1,252 @@
+//! Timestamps and release dates, as Spotify writes them.
+use serde_json::{json, Value};
+use std::time::{Duration, UNIX_EPOCH};
+
+use spotify_api::model::album::SimplifiedAlbum;
+use spotify_api::model::release_date::ReleaseDate;
+use spotify_api::model::timestamp::Timestamp;
+
+fn timestamp(s: &str) -> Timestamp {
+    s.parse()
+        .unwrap_or_else(|e| panic!("{:?} should parse: {}", s, e))
+}
+
+#[test]
+fn utc_timestamps() {
+    let t = timestamp("2020-06-13T12:34:56Z");
+    assert_eq!(t.to_string(), "2020-06-13T12:34:56Z");
+    assert_eq!(
+        timestamp("1970-01-01T00:00:01Z").system_time(),
+        UNIX_EPOCH + Duration::from_secs(1)
+    );
+    // RFC 3339 allows lowercase separators, and a space between date and time
+    assert_eq!(timestamp("2020-06-13t12:34:56z"), t);
+    assert_eq!(timestamp("2020-06-13 12:34:56Z"), t);
+}
+
+#[test]
+fn offsets_are_normalized_to_utc() {
+    assert_eq!(
+        timestamp("2020-06-13T14:34:56+02:00").to_string(),
+        "2020-06-13T12:34:56Z"
+    );
+    assert_eq!(
+        timestamp("2020-06-13T07:04:56-05:30").to_string(),
+        "2020-06-13T12:34:56Z"
+    );
+    // Offsets can move the date, even across years
+    assert_eq!(
+        timestamp("2020-01-01T01:00:00+02:00").to_string(),
+        "2019-12-31T23:00:00Z"
+    );
+    assert_eq!(
+        timestamp("2020-06-13T12:34:56+00:00"),
+        timestamp("2020-06-13T12:34:56Z")
+    );
+}
+
+#[test]
+fn fractional_seconds() {
+    assert_eq!(
+        timestamp("2020-06-13T12:34:56.5Z").to_string(),
+        "2020-06-13T12:34:56.5Z"
+    );
+    assert_eq!(
+        timestamp("2020-06-13T12:34:56.000Z").to_string(),
+        "2020-06-13T12:34:56Z"
+    );
+    assert_eq!(
+        timestamp("2020-06-13T12:34:56.123456789+01:00").to_string(),
+        "2020-06-13T11:34:56.123456789Z"
+    );
+    // Digits past nanoseconds are dropped
+    assert_eq!(
+        timestamp("2020-06-13T12:34:56.1234567891Z").to_string(),
+        "2020-06-13T12:34:56.123456789Z"
+    );
+    assert!(timestamp("2020-06-13T12:34:56.5Z") > timestamp("2020-06-13T12:34:56Z"));
+}
+
+#[test]
+fn instants_before_the_epoch_and_leap_days_and_seconds() {
+    assert_eq!(
+        timestamp("1969-12-31T23:59:59.25Z").to_string(),
+        "1969-12-31T23:59:59.25Z"
+    );
+    assert_eq!(
+        timestamp("1969-12-31T23:59:59.25Z").system_time(),
+        UNIX_EPOCH - Duration::from_millis(750)
+    );
+    assert_eq!(
+        timestamp("2020-02-29T00:00:00Z").to_string(),
+        "2020-02-29T00:00:00Z"
+    );
+    assert_eq!(
+        timestamp("2016-12-31T23:59:60Z").to_string(),
+        "2017-01-01T00:00:00Z"
+    );
+}
+
+#[test]
+fn malformed_timestamps() {
+    for invalid in &[
+        "",
+        "2020-06-13",
+        "2020-06-13T12:34:56",
+        "2020-06-13T12:34Z",
+        "2020/06/13T12:34:56Z",
+        "2020-06-13X12:34:56Z",
+        "2020-0a-13T12:34:56Z",
+        "2020-13-01T00:00:00Z",
+        "2020-00-01T00:00:00Z",
+        "2021-02-29T00:00:00Z",
+        "2020-04-31T00:00:00Z",
+        "2020-06-13T24:00:00Z",
+        "2020-06-13T12:60:00Z",
+        "2020-06-13T12:34:61Z",
+        "2020-06-13T12:34:56.Z",
+        "2020-06-13T12:34:56+2:00",
+        "2020-06-13T12:34:56+0200",
+        "2020-06-13T12:34:56+24:00",
+        "2020-06-13T12:34:56 UTC",
+        "2020-06-13T12:34:56Z ",
+        "２020-06-13T12:34:56Z",
+    ] {
+        assert!(invalid.parse::<Timestamp>().is_err(), "{:?}", invalid);
+    }
+}
+
+#[test]
+fn timestamps_serialize_in_utc() {
+    let t: Timestamp = serde_json::from_value(json!("2020-06-13T14:34:56.5+02:00")).unwrap();
+    assert_eq!(
+        serde_json::to_value(t).unwrap(),
+        json!("2020-06-13T12:34:56.5Z")
+    );
+    assert!(serde_json::from_value::<Timestamp>(json!("yesterday")).is_err());
+    assert!(serde_json::from_value::<Timestamp>(json!(1_592_051_696)).is_err());
+}
+
+fn release_date(date: &str, precision: &str) -> serde_json::Result<ReleaseDate> {
+    serde_json::from_value(json!({
+        "release_date": date,
+        "release_date_precision": precision,
+    }))
+}
+
+#[test]
+fn release_dates_of_every_precision() {
+    let year = release_date("1981", "year").unwrap();
+    assert_eq!(year, ReleaseDate::Year(1981));
+    assert_eq!((year.year(), year.month(), year.day()), (1981, None, None));
+    assert_eq!(
+        (year.precision(), year.to_string()),
+        ("year", "1981".into())
+    );
+
+    let month = release_date("1981-12", "month").unwrap();
+    assert_eq!(month, ReleaseDate::Month(1981, 12));
+    assert_eq!((month.month(), month.day()), (Some(12), None));
+    assert_eq!(
+        (month.precision(), month.to_string()),
+        ("month", "1981-12".into())
+    );
+
+    let day = release_date("2020-02-29", "day").unwrap();
+    assert_eq!(day, ReleaseDate::Day(2020, 2, 29));
+    assert_eq!((day.month(), day.day()), (Some(2), Some(29)));
+    assert_eq!(
+        (day.precision(), day.to_string()),
+        ("day", "2020-02-29".into())
+    );
+
+    for date in &[year, month, day] {
+        let value = serde_json::to_value(date).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "release_date": date.to_string(),
+                "release_date_precision": date.precision(),
+            })
+        );
+        assert_eq!(serde_json::from_value::<ReleaseDate>(value).unwrap(), *date);
+    }
+}
+
+#[test]
+fn invalid_release_dates() {
+    for (date, precision) in &[
+        ("", "year"),
+        ("19x1", "year"),
+        ("1981-12", "year"),
+        ("1981", "month"),
+        ("1981-13", "month"),
+        ("1981-00", "month"),
+        ("1981-12", "day"),
+        ("2021-02-29", "day"),
+        ("1981-12-00", "day"),
+        ("1981-12-32", "day"),
+        ("1981-12-01", "week"),
+    ] {
+        assert!(
+            release_date(date, precision).is_err(),
+            "{:?} with {:?} precision",
+            date,
+            precision
+        );
+    }
+}
+
+#[test]
+fn less_precise_dates_sort_first() {
+    let mut dates = vec![
+        ReleaseDate::Month(2020, 2),
+        ReleaseDate::Day(2020, 1, 15),
+        ReleaseDate::Year(2021),
+        ReleaseDate::Month(2020, 1),
+        ReleaseDate::Year(2020),
+    ];
+    dates.sort();
+    assert_eq!(
+        dates,
+        vec![
+            ReleaseDate::Year(2020),
+            ReleaseDate::Month(2020, 1),
+            ReleaseDate::Day(2020, 1, 15),
+            ReleaseDate::Month(2020, 2),
+            ReleaseDate::Year(2021),
+        ]
+    );
+}
+
+fn simplified_album() -> Value {
+    let track: Value = serde_json::from_str(include_str!("fixtures/track.json")).unwrap();
+    track["album"].clone()
+}
+
+#[test]
+fn album_release_dates_are_optional_but_must_be_valid() {
+    let album: SimplifiedAlbum = serde_json::from_value(simplified_album()).unwrap();
+    assert!(album.release_date.is_some());
+
+    // Albums of local files have no release date
+    let mut local = simplified_album();
+    local["release_date"] = Value::Null;
+    local["release_date_precision"] = Value::Null;
+    let album: SimplifiedAlbum = serde_json::from_value(local).unwrap();
+    assert!(album.release_date.is_none());
+    let value = serde_json::to_value(&album).unwrap();
+    assert!(value.get("release_date").is_none());
+    assert!(value.get("release_date_precision").is_none());
+
+    let mut invalid = simplified_album();
+    invalid["release_date"] = json!("2021-02-29");
+    invalid["release_date_precision"] = json!("day");
+    assert!(serde_json::from_value::<SimplifiedAlbum>(invalid).is_err());
+
+    let mut half = simplified_album();
+    half.as_object_mut()
+        .unwrap()
+        .remove("release_date_precision");
+    assert!(serde_json::from_value::<SimplifiedAlbum>(half).is_err());
+}
@@ -0,0 +1,101 @@
+//! Sharing identical GET requests that are in flight, against a client that answers slowly.
+#![cfg(feature = "api")]
+
+use futures::future::join_all;
+use http_client::Response;
+use http_types::{Method, StatusCode};
+use std::time::Duration;
+
+use spotify_api::api::dedup::DedupSpotifyClient;
+use spotify_api::api::{artists, follow};
+use spotify_api::model::id::ArtistId;
+
+mod common;
+use common::{fixture_with_id, respond, Fixtures, Stub};
+
+/// A client that answers `/artists/{id}` from the artist fixture and anything else with an
+/// empty success, after a short delay, along with what it was sent.
+fn client() -> (DedupSpotifyClient<Stub<Fixtures>>, Fixtures) {
+    let fixtures = Fixtures::new()
+        .route("/v1/artists/*", |sent| {
+            respond(
+                StatusCode::Ok,
+                fixture_with_id("artist", &sent.last_segment()),
+            )
+        })
+        .otherwise(|_| Response::new(StatusCode::Ok))
+        // Give concurrent callers a chance to find each request in flight
+        .delay(|_| Duration::from_millis(5));
+    (DedupSpotifyClient::new(Stub(fixtures.clone())), fixtures)
+}
+
+fn artist_id(n: usize) -> ArtistId {
+    format!("{:0>22}", n).parse().unwrap()
+}
+
+fn in_flight(client: &DedupSpotifyClient<Stub<Fixtures>>) -> String {
+    let debug = format!("{:?}", client);
+    debug[debug.find("in_flight").unwrap()..].to_owned()
+}
+
+#[async_std::test]
+async fn identical_gets_in_flight_are_sent_once() {
+    let (client, requests) = client();
+    let id = artist_id(1);
+
+    let results = join_all((0..4).map(|_| artists::artist(&client, &id))).await;
+    let again = artists::artist(&client, &id).await.unwrap();
+
+    for result in results {
+        assert_eq!(result.unwrap().id, id);
+    }
+    assert_eq!(again.id, id);
+    // Four concurrent calls shared one request; the later call sent its own
+    assert_eq!(requests.sent().len(), 2);
+    assert_eq!(in_flight(&client), "in_flight: 0 }");
+}
+
+#[async_std::test]
+async fn other_requests_are_not_shared() {
+    let (client, requests) = client();
+    let ids = [artist_id(1), artist_id(2)];
+
+    let results = join_all(ids.iter().map(|id| artists::artist(&client, id))).await;
+    for (id, result) in ids.iter().zip(results) {
+        assert_eq!(result.unwrap().id, *id);
+    }
+
+    let follows = join_all((0..2).map(|_| follow::follow_artists(&client, &ids))).await;
+    assert!(follows.into_iter().all(|r| r.is_ok()));
+
+    let methods: Vec<Method> = requests.sent().iter().map(|r| r.method).collect();
+    assert_eq!(
+        methods,
+        vec![Method::Get, Method::Get, Method::Put, Method::Put]
+    );
+}
+
+#[async_std::test]
+async fn requests_are_forgotten_when_every_caller_goes_away() {
+    let (client, requests) = client();
+    let id = artist_id(1);
+
+    {
+        let mut first = Box::pin(artists::artist(&client, &id));
+        let mut second = Box::pin(artists::artist(&client, &id));
+        assert!(futures::poll!(&mut first).is_pending());
+        assert!(futures::poll!(&mut second).is_pending());
+        assert_eq!(in_flight(&client), "in_flight: 1 }");
+        assert_eq!(requests.sent().len(), 1);
+
+        // One caller going away leaves the request to the other
+        drop(first);
+        assert_eq!(in_flight(&client), "in_flight: 1 }");
+    }
+    assert_eq!(in_flight(&client), "in_flight: 0 }");
+
+    // A request nobody waits for any more isn't joined
+    assert_eq!(artists::artist(&client, &id).await.unwrap().id, id);
+    assert_eq!(requests.sent().len(), 2);
+    assert_eq!(in_flight(&client), "in_flight: 0 }");
+}
@@ -0,0 +1,109 @@
+//! String enums, including values added by Spotify after this crate was written.
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+use spotify_api::model::device::DevicePayload;
+use spotify_api::model::recommend::Recommendations;
+use spotify_api::model::senum::{
+    AlbumType, CopyrightType, DeviceType, Product, RecommendationsSeedType, TimeRange,
+};
+
+#[test]
+fn known_values() {
+    let album: AlbumType = "appears_on".parse().unwrap();
+    assert_eq!(album, AlbumType::AppearsOn);
+    assert_eq!(album.as_str(), "appears_on");
+    assert_eq!(album.to_string(), "appears_on");
+    assert_eq!(serde_json::to_value(&album).unwrap(), json!("appears_on"));
+    assert_eq!(
+        serde_json::from_value::<AlbumType>(json!("appears_on")).unwrap(),
+        album
+    );
+
+    assert_eq!(
+        "P".parse::<CopyrightType>().unwrap(),
+        CopyrightType::Performance
+    );
+    assert_eq!(TimeRange::ShortTerm.as_str(), "short_term");
+    assert_eq!("free".parse::<Product>().unwrap(), Product::Free);
+}
+
+#[test]
+fn unknown_values_round_trip() {
+    let album: AlbumType = serde_json::from_value(json!("audiobook_group")).unwrap();
+    match &album {
+        AlbumType::Unknown(value) => assert_eq!(value, "audiobook_group"),
+        other => panic!("expected an unknown value, got {:?}", other),
+    }
+    assert_eq!(album.as_str(), "audiobook_group");
+    assert_eq!(album.to_string(), "audiobook_group");
+    assert_eq!(
+        serde_json::to_value(&album).unwrap(),
+        json!("audiobook_group")
+    );
+
+    // Unknown values are compared by their text
+    let again: AlbumType = "audiobook_group".parse().unwrap();
+    assert_eq!(album, again);
+    assert_ne!(album, "other_group".parse().unwrap());
+    let set: HashSet<AlbumType> = vec![album, again].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn known_values_are_never_unknown() {
+    for value in &["album", "single", "appears_on", "compilation"] {
+        let parsed: AlbumType = value.parse().unwrap();
+        assert!(
+            !matches!(parsed, AlbumType::Unknown(_)),
+            "{:?} parsed as unknown",
+            value
+        );
+        assert_eq!(parsed.as_str(), *value);
+    }
+    // An unknown value with the text of a known one is that value
+    let built = AlbumType::Unknown("single".to_owned());
+    assert_eq!(built, AlbumType::Single);
+    let set: HashSet<AlbumType> = vec![built, AlbumType::Single].into_iter().collect();
+    assert_eq!(set.len(), 1);
+    // Spellings are matched exactly
+    assert!(matches!(
+        "Album".parse::<AlbumType>().unwrap(),
+        AlbumType::Unknown(_)
+    ));
+}
+
+#[test]
+fn alternative_spellings_serialize_as_spotify_sends_them() {
+    for spelling in &["artist", "ARTIST"] {
+        let seed: RecommendationsSeedType = serde_json::from_value(json!(spelling)).unwrap();
+        assert_eq!(seed, RecommendationsSeedType::Artist);
+        assert_eq!(serde_json::to_value(&seed).unwrap(), json!("artist"));
+    }
+    assert_eq!(RecommendationsSeedType::Genre.as_str(), "genre");
+}
+
+#[test]
+fn responses_with_new_values_still_deserialize() {
+    let mut devices: Value = serde_json::from_str(include_str!("fixtures/devices.json")).unwrap();
+    devices["devices"][0]["type"] = json!("Fridge");
+
+    let payload: DevicePayload = serde_json::from_value(devices).unwrap();
+    let device_type = &payload.devices[0].type_;
+    assert_eq!(device_type.as_str(), "Fridge");
+    assert!(matches!(device_type, DeviceType::Unknown(_)));
+
+    let serialized = serde_json::to_value(&payload).unwrap();
+    assert_eq!(serialized["devices"][0]["type"], json!("Fridge"));
+}
+
+#[test]
+fn recommendation_seeds_serialize_in_lower_case() {
+    let recommendations: Recommendations =
+        serde_json::from_str(include_str!("fixtures/recommendations.json")).unwrap();
+    let seed = &recommendations.seeds[0];
+    assert_eq!(seed.type_, RecommendationsSeedType::Artist);
+
+    let value = serde_json::to_value(&recommendations).unwrap();
+    assert_eq!(value["seeds"][0]["type"], json!("artist"));
+}
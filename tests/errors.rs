@@ -0,0 +1,94 @@
+//! Turning unsuccessful responses into `Error::Api`.
+#![cfg(feature = "api")]
+
+use http_client::Response;
+use http_types::StatusCode;
+
+use spotify_api::api::{albums, library};
+use spotify_api::model::id::{AlbumId, TrackId};
+
+mod common;
+use common::{Fixtures, Stub};
+
+/// Answers every request with `status` and `body`.
+fn responder(status: StatusCode, body: &'static str) -> Stub<Fixtures> {
+    Stub(Fixtures::new().otherwise(move |_| {
+        let mut resp = Response::new(status);
+        resp.set_body(body);
+        resp
+    }))
+}
+
+fn album_id() -> AlbumId {
+    "4MnIC5UuY8BzHI0yvFU5m3".parse().unwrap()
+}
+
+fn track_ids() -> Vec<TrackId> {
+    vec!["11dFghVXANMlKmJXsNCbNl".parse().unwrap()]
+}
+
+fn api_error<T: std::fmt::Debug>(result: spotify_api::Result<T>) -> (StatusCode, String) {
+    match result {
+        Err(spotify_api::Error::Api { status, message }) => (status, message),
+        other => panic!("expected an API error, got {:?}", other),
+    }
+}
+
+#[async_std::test]
+async fn regular_error_objects() {
+    let client = responder(
+        StatusCode::NotFound,
+        r#"{ "error": { "status": 404, "message": "Non existing id" } }"#,
+    );
+
+    let (status, message) = api_error(albums::album(&client, &album_id()).await);
+    assert_eq!(status, StatusCode::NotFound);
+    assert_eq!(message, "Non existing id");
+}
+
+#[async_std::test]
+async fn authentication_errors() {
+    let client = responder(
+        StatusCode::BadRequest,
+        r#"{ "error": "invalid_client", "error_description": "Invalid client" }"#,
+    );
+
+    let (status, message) = api_error(albums::album(&client, &album_id()).await);
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(message, "Invalid client");
+}
+
+#[async_std::test]
+async fn bodies_that_dont_parse_are_kept_as_the_message() {
+    let client = responder(StatusCode::BadGateway, "<html>Bad gateway</html>");
+
+    let (status, message) = api_error(albums::album(&client, &album_id()).await);
+    assert_eq!(status, StatusCode::BadGateway);
+    assert_eq!(message, "<html>Bad gateway</html>");
+
+    let client = responder(StatusCode::ServiceUnavailable, "");
+    let (status, message) = api_error(albums::album(&client, &album_id()).await);
+    assert_eq!(status, StatusCode::ServiceUnavailable);
+    assert_eq!(message, "");
+}
+
+#[async_std::test]
+async fn mutations_fail_on_error_statuses() {
+    let client = responder(
+        StatusCode::Forbidden,
+        r#"{ "error": { "status": 403, "message": "Insufficient client scope" } }"#,
+    );
+
+    let (status, message) = api_error(library::library_save_tracks(&client, &track_ids()).await);
+    assert_eq!(status, StatusCode::Forbidden);
+    assert_eq!(message, "Insufficient client scope");
+}
+
+#[async_std::test]
+async fn mutations_ignore_successful_bodies() {
+    let client = responder(StatusCode::Ok, "");
+
+    library::library_save_tracks(&client, &track_ids())
+        .await
+        .unwrap();
+}
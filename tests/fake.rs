@@ -0,0 +1,328 @@
+//! The crate's endpoints, end to end against the in-memory fake API.
+#![cfg(feature = "testing")]
+
+use futures::TryStreamExt;
+use http_types::StatusCode;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::{Duration, SystemTime};
+
+use spotify_api::api::playlists::ItemPositions;
+use spotify_api::api::{follow, library, player, playlists};
+use spotify_api::model::artist::FullArtist;
+use spotify_api::model::id::{ArtistId, PlaylistId, TrackId};
+
+mod common;
+use common::*;
+
+#[async_std::test]
+async fn saved_tracks_show_up_in_the_library() {
+    let spotify = spotify();
+    let ids: Vec<TrackId> = (1..=3).map(track_id).collect();
+
+    library::library_save_tracks(&spotify, &ids[..2])
+        .await
+        .unwrap();
+    let contains = library::library_contains_tracks(&spotify, &ids)
+        .await
+        .unwrap();
+    assert_eq!(contains, vec![true, true, false]);
+
+    library::library_remove_tracks(&spotify, &ids[..1])
+        .await
+        .unwrap();
+    let saved = library::library_get_tracks(&spotify).await.unwrap();
+    assert_eq!(saved.total, 1);
+    assert_eq!(saved.items[0].track.id, Some(ids[1].clone()));
+
+    // Only tracks the catalog knows can be saved
+    assert_status(
+        library::library_save_tracks(&spotify, &[track_id(99)]).await,
+        StatusCode::BadRequest,
+    );
+}
+
+#[async_std::test]
+async fn pages_link_to_the_next_page() {
+    let spotify = spotify();
+    let ids: Vec<TrackId> = (1..=10).map(track_id).collect();
+    library::library_save_tracks(&spotify, &ids).await.unwrap();
+
+    let first = library::library_get_tracks_with_options(&spotify, Some(4), None, None)
+        .await
+        .unwrap();
+    assert_eq!((first.items.len(), first.total), (4, 10));
+    assert!(first.previous.is_none());
+
+    let all: Vec<_> = first
+        .into_stream(&spotify)
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    let mut seen: Vec<TrackId> = all.into_iter().map(|s| s.track.id.unwrap()).collect();
+    seen.sort();
+    assert_eq!(seen, ids);
+    // The first page, then two more
+    assert_eq!(spotify.requests().len(), 4);
+}
+
+#[async_std::test]
+async fn followed_artists_are_paged_by_cursor() {
+    let spotify = spotify();
+    let ids: Vec<ArtistId> = (1..=5).map(|n| id(n).parse().unwrap()).collect();
+
+    follow::follow_artists(&spotify, &ids).await.unwrap();
+    follow::unfollow_artists(&spotify, &ids[4..]).await.unwrap();
+    let follows = follow::user_follows_artists(&spotify, &ids).await.unwrap();
+    assert_eq!(follows, vec![true, true, true, true, false]);
+
+    let followed: Vec<FullArtist> = follow::user_followed_artists_stream(&spotify, Some(3))
+        .try_collect()
+        .await
+        .unwrap();
+    let followed: Vec<ArtistId> = followed.into_iter().map(|a| a.id).collect();
+    assert_eq!(followed, ids[..4]);
+}
+
+#[async_std::test]
+async fn playlist_edits_are_kept() {
+    let spotify = spotify();
+    let playlist = playlists::create_playlist(&spotify, &me(), "Road trip")
+        .await
+        .unwrap();
+    let id = playlist.id;
+
+    let uris: Vec<String> = (1..=4).map(track_uri).collect();
+    playlists::add_items(&spotify, &id, &uris).await.unwrap();
+    playlists::add_items_with_options(&spotify, &id, &[track_uri(5)], Some(0))
+        .await
+        .unwrap();
+    // 5 1 2 3 4, then move 5 to the end
+    let moved = playlists::reorder_items(&spotify, &id, 0, 5).await.unwrap();
+
+    // A position taken from an older snapshot is refused
+    let stale = playlists::remove_items_at_positions(
+        &spotify,
+        &id,
+        &[ItemPositions {
+            uri: &track_uri(2),
+            positions: Some(&[1]),
+        }],
+        Some(&playlist.snapshot_id),
+    )
+    .await;
+    assert_status(stale, StatusCode::BadRequest);
+    playlists::remove_items_at_positions(
+        &spotify,
+        &id,
+        &[ItemPositions {
+            uri: &track_uri(2),
+            positions: Some(&[1]),
+        }],
+        Some(&moved.snapshot_id),
+    )
+    .await
+    .unwrap();
+
+    let items = playlists::playlist_items(&spotify, &id).await.unwrap();
+    let order: Vec<String> = items
+        .items
+        .iter()
+        .map(|i| i.track.as_ref().unwrap().uri.clone())
+        .collect();
+    assert_eq!(
+        order,
+        vec![track_uri(1), track_uri(3), track_uri(4), track_uri(5)]
+    );
+
+    playlists::change_playlist_details(&spotify, &id, Some("Day trip"), None, None, None)
+        .await
+        .unwrap();
+    let mine = playlists::current_user_playlists(&spotify).await.unwrap();
+    assert_eq!(mine.items.len(), 1);
+    assert_eq!(mine.items[0].name, "Day trip");
+    assert_eq!(mine.items[0].tracks.total, 4);
+
+    playlists::replace_items::<_, &str>(&spotify, &id, &[])
+        .await
+        .unwrap();
+    follow::unfollow_playlist(&spotify, &id).await.unwrap();
+    assert_eq!(
+        playlists::playlist(&spotify, &id)
+            .await
+            .unwrap()
+            .tracks
+            .total,
+        0
+    );
+    assert!(playlists::current_user_playlists(&spotify)
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+}
+
+#[async_std::test]
+async fn out_of_range_reorders_are_refused() {
+    let spotify = spotify();
+    let id = playlists::create_playlist(&spotify, &me(), "Road trip")
+        .await
+        .unwrap()
+        .id;
+    let uris: Vec<String> = (1..=3).map(track_uri).collect();
+    playlists::add_items(&spotify, &id, &uris).await.unwrap();
+
+    // Ranges that run past the end, including ones whose end doesn't fit in a usize
+    let ranges = [(2, Some(2)), (1, Some(usize::MAX)), (usize::MAX, None)];
+    for &(start, length) in &ranges {
+        let reordered =
+            playlists::reorder_items_with_options(&spotify, &id, start, 0, length, None).await;
+        assert_status(reordered, StatusCode::BadRequest);
+    }
+}
+
+#[async_std::test]
+async fn seeded_playlists_belong_to_their_owner() {
+    let spotify = spotify();
+    let playlist: spotify_api::model::playlist::FullPlaylist =
+        serde_json::from_str(include_str!("fixtures/playlist.json")).unwrap();
+    let playlist_id = playlist.id.clone();
+    spotify.add_playlist(playlist);
+
+    let seeded = playlists::playlist(&spotify, &playlist_id).await.unwrap();
+    assert_eq!(seeded.owner.id, "spotify".parse().unwrap());
+    assert_status(
+        playlists::add_items(&spotify, &playlist_id, &[track_uri(1)]).await,
+        StatusCode::Forbidden,
+    );
+
+    follow::follow_playlist(&spotify, &playlist_id)
+        .await
+        .unwrap();
+    let followers = follow::users_follow_playlist(&spotify, &playlist_id, &[me()])
+        .await
+        .unwrap();
+    assert_eq!(followers, vec![true]);
+    let unknown: PlaylistId = id(77).parse().unwrap();
+    assert_status(
+        playlists::playlist(&spotify, &unknown).await,
+        StatusCode::NotFound,
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct SlimTrack {
+    id: TrackId,
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct SlimItem {
+    track: SlimTrack,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlimPage {
+    items: Vec<SlimItem>,
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlimPlaylist {
+    name: String,
+    tracks: SlimPage,
+}
+
+#[async_std::test]
+async fn fields_select_part_of_a_playlist() {
+    let spotify = spotify();
+    let playlist = playlists::create_playlist(&spotify, &me(), "Slim")
+        .await
+        .unwrap();
+    let uris: Vec<String> = (1..=3).map(track_uri).collect();
+    playlists::add_items(&spotify, &playlist.id, &uris)
+        .await
+        .unwrap();
+
+    let slim: SlimPlaylist = playlists::playlist_with_options(
+        &spotify,
+        &playlist.id,
+        Some("name,tracks.items(track(id,name)),tracks.next"),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(slim.name, "Slim");
+    assert_eq!(slim.tracks.items.len(), 3);
+    assert_eq!(slim.tracks.items[2].track.id, track_id(3));
+
+    let page: SlimPage = playlists::playlist_items_with_options(
+        &spotify,
+        &playlist.id,
+        Some("items(track(id,name)),next"),
+        Some(2),
+        Some(0),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(page.items.len(), 2);
+    assert!(page.next.unwrap().contains("fields="));
+
+    // Nothing but the requested fields is sent back
+    let raw: Value = playlists::playlist_items_with_options(
+        &spotify,
+        &playlist.id,
+        Some("items(track(id))"),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(raw["items"][0], json!({ "track": { "id": id(1) } }));
+    assert_eq!(raw.as_object().unwrap().len(), 1);
+}
+
+#[async_std::test]
+async fn queue_and_history() {
+    let spotify = spotify();
+    spotify.set_currently_playing(Some(&track_uri(1)));
+    let failed =
+        player::add_many_to_queue(&spotify, &[track_uri(2), track_uri(99), track_uri(3)], None)
+            .await;
+    assert_eq!(failed.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![1]);
+
+    let queue = player::queue(&spotify).await.unwrap();
+    assert!(queue.currently_playing.is_some());
+    assert_eq!(queue.queue.len(), 2);
+
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    for n in 1..=5 {
+        spotify.add_played(track(n), start + Duration::from_secs(n as u64 * 60));
+    }
+    let history: Vec<_> = player::recently_played_stream(&spotify, Some(2), None)
+        .try_collect()
+        .await
+        .unwrap();
+    let played: Vec<TrackId> = history.into_iter().map(|h| h.track.id.unwrap()).collect();
+    assert_eq!(played, (1..=5).rev().map(track_id).collect::<Vec<_>>());
+}
+
+#[async_std::test]
+async fn injected_errors() {
+    let spotify = spotify();
+    spotify.fail_next(StatusCode::TooManyRequests);
+    spotify.fail_next(StatusCode::ServiceUnavailable);
+
+    assert_status(
+        library::library_get_tracks(&spotify).await,
+        StatusCode::TooManyRequests,
+    );
+    assert_status(
+        library::library_get_tracks(&spotify).await,
+        StatusCode::ServiceUnavailable,
+    );
+    assert!(library::library_get_tracks(&spotify).await.is_ok());
+}
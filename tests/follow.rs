@@ -0,0 +1,105 @@
+//! Following artists, users and playlists, against the in-memory fake API.
+#![cfg(feature = "testing")]
+
+use futures::TryStreamExt;
+
+use spotify_api::api::{follow, playlists};
+use spotify_api::model::artist::FullArtist;
+use spotify_api::model::id::{ArtistId, UserId};
+
+mod common;
+use common::*;
+
+#[async_std::test]
+async fn follows_are_split_at_each_limit() {
+    let spotify = spotify();
+    for n in 6..=60 {
+        spotify.add_artist(fixture::<FullArtist>("artist", &id(n)));
+    }
+
+    follow::user_follows_artists(&spotify, &ids::<ArtistId>(51))
+        .await
+        .unwrap();
+    follow::follow_artists(&spotify, &ids::<ArtistId>(60))
+        .await
+        .unwrap();
+    assert_eq!(id_counts(&spotify), vec![50, 1, 50, 10]);
+
+    let requests = spotify.requests();
+    assert!(requests[2..]
+        .iter()
+        .all(|r| r.starts_with("PUT /v1/me/following?type=artist&ids=")));
+    let follows = follow::user_follows_artists(&spotify, &ids::<ArtistId>(60))
+        .await
+        .unwrap();
+    assert!(follows.into_iter().all(|f| f));
+}
+
+#[async_std::test]
+async fn unfollows_are_split_at_each_limit() {
+    let spotify = spotify();
+    let users: Vec<UserId> = ids(60);
+    follow::follow_users(&spotify, &users).await.unwrap();
+
+    follow::unfollow_users(&spotify, &users[..55])
+        .await
+        .unwrap();
+    follow::unfollow_artists(&spotify, &ids::<ArtistId>(5))
+        .await
+        .unwrap();
+
+    assert_eq!(id_counts(&spotify), vec![50, 10, 50, 5, 5]);
+    let requests = spotify.requests();
+    assert!(requests[2..4]
+        .iter()
+        .all(|r| r.starts_with("DELETE /v1/me/following?type=user&ids=")));
+    let follows = follow::user_follows_users(&spotify, &users).await.unwrap();
+    let expected: Vec<bool> = (1..=60).map(|n| n > 55).collect();
+    assert_eq!(follows, expected);
+}
+
+#[async_std::test]
+async fn playlist_followers_are_checked_5_at_a_time() {
+    let spotify = spotify();
+    let playlist = playlists::create_playlist(&spotify, &me(), "Road trip")
+        .await
+        .unwrap();
+    let mut users: Vec<UserId> = ids(11);
+    users.insert(7, me());
+
+    let follows = follow::users_follow_playlist(&spotify, &playlist.id, &users)
+        .await
+        .unwrap();
+
+    // The fake refuses more than 5 users at once
+    assert_eq!(id_counts(&spotify)[1..], [5, 5, 2]);
+    let expected: Vec<bool> = (0..12).map(|i| i == 7).collect();
+    assert_eq!(follows, expected);
+}
+
+#[async_std::test]
+async fn followed_artists_stream_follows_the_after_cursor() {
+    let spotify = spotify();
+    for n in 6..=7 {
+        spotify.add_artist(fixture::<FullArtist>("artist", &id(n)));
+    }
+    let artists: Vec<ArtistId> = ids(7);
+    follow::follow_artists(&spotify, &artists).await.unwrap();
+
+    let followed: Vec<FullArtist> = follow::user_followed_artists_stream(&spotify, Some(3))
+        .try_collect()
+        .await
+        .unwrap();
+
+    let followed: Vec<ArtistId> = followed.into_iter().map(|a| a.id).collect();
+    assert_eq!(followed, artists);
+    let path = "GET /v1/me/following?type=artist&limit=3";
+    assert_eq!(
+        spotify.requests()[1..],
+        [
+            path.to_owned(),
+            format!("{}&after={}", path, id(3)),
+            format!("{}&after={}", path, id(6)),
+        ]
+    );
+}
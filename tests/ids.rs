@@ -0,0 +1,203 @@
+//! Parsing typed IDs from bare IDs, URIs and open.spotify.com URLs.
+use serde_json::json;
+
+use spotify_api::model::id::{AlbumId, Id, IdError, PlaylistId, TrackId, Uri, UserId};
+
+const TRACK: &str = "6rqhFgbbKwnb9MLmUQDhG6";
+const PLAYLIST: &str = "37i9dQZF1DXcBWIGoYBM5M";
+
+fn parse<I: std::str::FromStr<Err = IdError>>(s: &str) -> Result<I, IdError> {
+    s.parse()
+}
+
+#[test]
+fn bare_ids_must_be_22_base62_characters() {
+    let id: TrackId = TRACK.parse().unwrap();
+    assert_eq!(id.as_str(), TRACK);
+    assert_eq!(id.to_string(), TRACK);
+
+    for invalid in &[
+        "",
+        "6rqhFgbbKwnb9MLmUQDhG",
+        "6rqhFgbbKwnb9MLmUQDhG6x",
+        "6rqhFgbbKwnb9MLmUQDh-6",
+        "6rqhFgbbKwnb9MLmUQDh 6",
+        "6rqhFgbbKwnb9MLmUQDhé",
+    ] {
+        assert_eq!(
+            parse::<TrackId>(invalid),
+            Err(IdError::InvalidId(invalid.to_string())),
+            "{:?}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn uris() {
+    let uri = format!("spotify:track:{}", TRACK);
+    let id: TrackId = uri.parse().unwrap();
+    assert_eq!(id.as_str(), TRACK);
+    assert_eq!(id.uri(), uri);
+    assert_eq!(TrackId::from_uri(&uri).unwrap(), id);
+
+    assert_eq!(
+        parse::<AlbumId>(&uri),
+        Err(IdError::WrongType {
+            expected: "album",
+            found: "track".to_owned(),
+        })
+    );
+    assert_eq!(
+        parse::<TrackId>("spotify:track:abc"),
+        Err(IdError::InvalidId("abc".to_owned()))
+    );
+    for invalid in &[
+        "spotify:",
+        "spotify:track",
+        "spotify:track:a:b",
+        "spotify:user:bob:album:37i9dQZF1DXcBWIGoYBM5M",
+    ] {
+        assert_eq!(
+            parse::<TrackId>(invalid),
+            Err(IdError::InvalidUri(invalid.to_string())),
+            "{:?}",
+            invalid
+        );
+    }
+    // `from_uri` doesn't fall back to other forms
+    assert!(TrackId::from_uri(TRACK).is_err());
+}
+
+#[test]
+fn legacy_playlist_uris() {
+    let uri = format!("spotify:user:spotify:playlist:{}", PLAYLIST);
+    let id: PlaylistId = uri.parse().unwrap();
+    assert_eq!(id.as_str(), PLAYLIST);
+    // The owner is dropped from the canonical URI
+    assert_eq!(id.uri(), format!("spotify:playlist:{}", PLAYLIST));
+
+    assert_eq!(
+        parse::<UserId>(&uri),
+        Err(IdError::WrongType {
+            expected: "user",
+            found: "playlist".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn urls() {
+    let expected: TrackId = TRACK.parse().unwrap();
+    for url in &[
+        format!("https://open.spotify.com/track/{}", TRACK),
+        format!("http://open.spotify.com/track/{}", TRACK),
+        format!("https://open.spotify.com/track/{}?si=abc123", TRACK),
+        format!("https://open.spotify.com/track/{}#share", TRACK),
+        format!("https://open.spotify.com/track/{}/", TRACK),
+        format!("https://open.spotify.com/intl-de/track/{}", TRACK),
+        format!(
+            "https://open.spotify.com/embed/track/{}?utm_source=x",
+            TRACK
+        ),
+    ] {
+        assert_eq!(parse::<TrackId>(url), Ok(expected.clone()), "{}", url);
+    }
+    assert_eq!(
+        expected.url(),
+        format!("https://open.spotify.com/track/{}", TRACK)
+    );
+
+    let legacy = format!(
+        "https://open.spotify.com/user/spotify/playlist/{}",
+        PLAYLIST
+    );
+    assert_eq!(parse::<PlaylistId>(&legacy).unwrap().as_str(), PLAYLIST);
+
+    assert_eq!(
+        parse::<AlbumId>(&format!("https://open.spotify.com/track/{}", TRACK)),
+        Err(IdError::WrongType {
+            expected: "album",
+            found: "track".to_owned(),
+        })
+    );
+    for invalid in &[
+        format!("https://play.spotify.com/track/{}", TRACK),
+        format!("https://open.spotify.com.evil.com/track/{}", TRACK),
+        "https://open.spotify.com/".to_owned(),
+        "https://open.spotify.com/track".to_owned(),
+        format!("https://open.spotify.com/intl-de/embed/track/{}", TRACK),
+    ] {
+        assert_eq!(
+            parse::<TrackId>(invalid),
+            Err(IdError::InvalidUrl(invalid.clone())),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn user_ids_can_be_usernames() {
+    for valid in &["smedjan", "wizzler", "1234567890", "first.last_name-99"] {
+        let id: UserId = valid.parse().unwrap();
+        assert_eq!(id.as_str(), *valid);
+    }
+    let id: UserId = "spotify:user:smedjan".parse().unwrap();
+    assert_eq!(id.as_str(), "smedjan");
+    let id: UserId = "https://open.spotify.com/user/smedjan?si=1"
+        .parse()
+        .unwrap();
+    assert_eq!(id.as_str(), "smedjan");
+
+    for invalid in &["", "two words", "a:b", "a/b", "a?b", "a#b"] {
+        assert!(parse::<UserId>(invalid).is_err(), "{:?}", invalid);
+    }
+}
+
+#[test]
+fn ids_serialize_bare_and_deserialize_from_any_form() {
+    let id: TrackId = TRACK.parse().unwrap();
+    assert_eq!(serde_json::to_value(&id).unwrap(), json!(TRACK));
+
+    for form in &[
+        TRACK.to_owned(),
+        format!("spotify:track:{}", TRACK),
+        format!("https://open.spotify.com/track/{}", TRACK),
+    ] {
+        let parsed: TrackId = serde_json::from_value(json!(form)).unwrap();
+        assert_eq!(parsed, id);
+    }
+
+    assert!(serde_json::from_value::<TrackId>(json!("nope")).is_err());
+    assert!(serde_json::from_value::<AlbumId>(json!(format!("spotify:track:{}", TRACK))).is_err());
+}
+
+#[test]
+fn typed_uris() {
+    let text = format!("spotify:track:{}", TRACK);
+    let uri: Uri<TrackId> = text.parse().unwrap();
+    assert_eq!(uri.id().as_str(), TRACK);
+    assert_eq!(uri.to_string(), text);
+    assert_eq!(Uri::from(uri.clone().into_id()), uri);
+
+    assert_eq!(serde_json::to_value(&uri).unwrap(), json!(text));
+    let parsed: Uri<TrackId> = serde_json::from_value(json!(text)).unwrap();
+    assert_eq!(parsed, uri);
+
+    // Only URIs of the right kind are accepted
+    assert!(TRACK.parse::<Uri<TrackId>>().is_err());
+    assert!(format!("https://open.spotify.com/track/{}", TRACK)
+        .parse::<Uri<TrackId>>()
+        .is_err());
+    assert!(serde_json::from_value::<Uri<AlbumId>>(json!(text)).is_err());
+
+    // Legacy playlist URIs are read, but written in the current form
+    let legacy: Uri<PlaylistId> = format!("spotify:user:spotify:playlist:{}", PLAYLIST)
+        .parse()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&legacy).unwrap(),
+        json!(format!("spotify:playlist:{}", PLAYLIST))
+    );
+}
@@ -0,0 +1,170 @@
+//! Saving to and reading from the library, against the in-memory fake API.
+#![cfg(feature = "testing")]
+
+use serde_json::Value;
+
+use spotify_api::api::library;
+use spotify_api::model::album::FullAlbum;
+use spotify_api::model::audiobook::SimplifiedAudiobook;
+use spotify_api::model::id::{AlbumId, AudiobookId, EpisodeId, TrackId};
+use spotify_api::model::show::FullEpisode;
+
+mod common;
+use common::*;
+
+#[async_std::test]
+async fn reads_are_split_at_each_limit() {
+    let spotify = spotify();
+
+    library::library_contains_albums(&spotify, &ids::<AlbumId>(45))
+        .await
+        .unwrap();
+    library::library_contains_tracks(&spotify, &ids::<TrackId>(120))
+        .await
+        .unwrap();
+    assert_eq!(id_counts(&spotify), vec![20, 20, 5, 50, 50, 20]);
+
+    // Nothing is sent for no IDs
+    let contains = library::library_contains_tracks(&spotify, &[])
+        .await
+        .unwrap();
+    assert!(contains.is_empty());
+    assert_eq!(spotify.requests().len(), 6);
+}
+
+#[async_std::test]
+async fn reads_keep_the_order_of_ids() {
+    let spotify = spotify_with_tracks(120);
+    let saved: Vec<TrackId> = ids::<TrackId>(120).into_iter().step_by(3).collect();
+    library::library_save_tracks(&spotify, &saved)
+        .await
+        .unwrap();
+
+    let contains = library::library_contains_tracks(&spotify, &ids(120))
+        .await
+        .unwrap();
+
+    let expected: Vec<bool> = (1..=120).map(|n| n % 3 == 1).collect();
+    assert_eq!(contains, expected);
+}
+
+#[async_std::test]
+async fn mutations_are_split_at_each_limit() {
+    let spotify = spotify_with_tracks(101);
+    for album in ids::<AlbumId>(21) {
+        spotify.add_album(fixture::<FullAlbum>("album", album.as_ref()));
+    }
+
+    library::library_save_albums(&spotify, &ids(21))
+        .await
+        .unwrap();
+    library::library_remove_tracks(&spotify, &ids(101))
+        .await
+        .unwrap();
+
+    assert_eq!(id_counts(&spotify), vec![20, 1, 50, 50, 1]);
+    let saved = library::library_get_albums(&spotify).await.unwrap();
+    assert_eq!(saved.total, 21);
+}
+
+/// The audiobook fixture without its chapters, as the library lists audiobooks.
+fn audiobook(n: usize) -> SimplifiedAudiobook {
+    let mut audiobook: Value = fixture("audiobook", &id(n));
+    audiobook.as_object_mut().unwrap().remove("chapters");
+    serde_json::from_value(audiobook).unwrap()
+}
+
+#[async_std::test]
+async fn audiobooks_are_saved_checked_and_removed() {
+    let spotify = spotify();
+    for n in 1..=60 {
+        spotify.add_audiobook(audiobook(n));
+    }
+    let audiobooks = ids::<AudiobookId>(60);
+
+    library::library_save_audiobooks(&spotify, &audiobooks[..55])
+        .await
+        .unwrap();
+    library::library_remove_audiobooks(&spotify, &audiobooks[..2])
+        .await
+        .unwrap();
+    let contains = library::library_contains_audiobooks(&spotify, &audiobooks)
+        .await
+        .unwrap();
+
+    let expected: Vec<bool> = (1..=60).map(|n| n > 2 && n <= 55).collect();
+    assert_eq!(contains, expected);
+    let saved = library::library_get_audiobooks(&spotify).await.unwrap();
+    assert_eq!(saved.total, 53);
+
+    let requests = spotify.requests();
+    let methods: Vec<&str> = requests
+        .iter()
+        .map(|r| r.split(' ').next().unwrap())
+        .collect();
+    assert_eq!(methods, vec!["PUT", "PUT", "DELETE", "GET", "GET", "GET"]);
+    assert!(requests[..5]
+        .iter()
+        .all(|r| r.contains(" /v1/me/audiobooks")));
+    assert_eq!(id_counts(&spotify)[..5], [50, 5, 2, 50, 10]);
+}
+
+#[async_std::test]
+async fn albums_are_split_20_at_a_time() {
+    let spotify = spotify();
+    for album in ids::<AlbumId>(21) {
+        spotify.add_album(fixture::<FullAlbum>("album", album.as_ref()));
+    }
+    let albums = ids::<AlbumId>(21);
+
+    library::library_save_albums(&spotify, &albums)
+        .await
+        .unwrap();
+    library::library_remove_albums(&spotify, &albums[1..])
+        .await
+        .unwrap();
+    let contains = library::library_contains_albums(&spotify, &albums)
+        .await
+        .unwrap();
+
+    assert_eq!(id_counts(&spotify), vec![20, 1, 20, 20, 1]);
+    let mut expected = vec![false; 21];
+    expected[0] = true;
+    assert_eq!(contains, expected);
+}
+
+#[async_std::test]
+async fn episodes_are_split_50_at_a_time() {
+    let spotify = spotify();
+    for episode in ids::<EpisodeId>(51) {
+        spotify.add_episode(fixture::<FullEpisode>("episode", episode.as_ref()));
+    }
+    let episodes = ids::<EpisodeId>(51);
+
+    library::library_save_episodes(&spotify, &episodes)
+        .await
+        .unwrap();
+    library::library_remove_episodes(&spotify, &episodes[..1])
+        .await
+        .unwrap();
+    let contains = library::library_contains_episodes(&spotify, &episodes)
+        .await
+        .unwrap();
+    let saved =
+        library::library_get_episodes_with_options(&spotify, Some(50), Some(10), Some("SE"))
+            .await
+            .unwrap();
+
+    assert_eq!(id_counts(&spotify), vec![50, 1, 1, 50, 1, 0]);
+    let expected: Vec<bool> = (1..=51).map(|n| n > 1).collect();
+    assert_eq!(contains, expected);
+    assert_eq!(saved.total, 50);
+    assert_eq!(saved.items.len(), 40);
+    assert!(spotify.requests()[..5]
+        .iter()
+        .all(|r| r.contains(" /v1/me/episodes")));
+    assert_eq!(
+        spotify.requests()[5],
+        "GET /v1/me/episodes?limit=50&offset=10&market=SE"
+    );
+}
@@ -0,0 +1,233 @@
+//! Deserializes recorded API responses into the models.
+//!
+//! Run with `--features strict` as well to check that the fixtures contain no fields the models
+//! don't know about.
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+use spotify_api::model::album::{FullAlbum, FullAlbums, NewReleases, SavedAlbum, SimplifiedAlbum};
+use spotify_api::model::artist::{FollowedArtists, FullArtist, FullArtists, RelatedArtists};
+use spotify_api::model::audio::{AudioAnalysis, AudioFeatures, AudioFeaturesPayload};
+use spotify_api::model::audiobook::{
+    FullAudiobook, FullAudiobooks, FullChapter, FullChapters, SimplifiedAudiobook,
+};
+use spotify_api::model::category::{Category, PageCategory};
+use spotify_api::model::context::{CurrentlyPlaybackContext, CurrentlyPlayingContext};
+use spotify_api::model::cud_result::CUDResult;
+use spotify_api::model::device::DevicePayload;
+use spotify_api::model::image::Image;
+use spotify_api::model::market::Markets;
+use spotify_api::model::page::{CursorBasedPage, Page};
+use spotify_api::model::playing::{PlayHistory, Queue};
+use spotify_api::model::playlist::{
+    FeaturedPlaylists, FullPlaylist, PlaylistTrack, SimplifiedPlaylist,
+};
+use spotify_api::model::recommend::{GenreSeeds, Recommendations};
+use spotify_api::model::search::SearchResults;
+use spotify_api::model::show::{
+    FullEpisode, FullEpisodes, FullShow, SavedEpisode, SeversalSimplifiedShows, Show,
+};
+use spotify_api::model::track::{ArtistTopTracks, FullTrack, FullTracks, SavedTrack};
+use spotify_api::model::user::{PrivateUser, PublicUser};
+
+const UNKNOWN_FIELD: &str = "field_added_by_spotify";
+
+fn fixture(name: &str) -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+        .with_extension("json");
+    let text = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+/// Adds an unrecognized field to the outermost object of a response.
+fn with_unknown_field(mut value: Value) -> Value {
+    let object = match &mut value {
+        Value::Array(items) => &mut items[0],
+        object => object,
+    };
+    object
+        .as_object_mut()
+        .expect("fixture is not an object")
+        .insert(UNKNOWN_FIELD.to_owned(), json!({ "nested": [1, 2, 3] }));
+    value
+}
+
+/// Deserializes a fixture, and checks that serializing the model gives back the same model.
+fn round_trip<T: DeserializeOwned + Serialize>(name: &str) -> T {
+    let model: T = serde_json::from_value(fixture(name))
+        .unwrap_or_else(|e| panic!("{}.json doesn't match the model: {}", name, e));
+    let serialized = serde_json::to_value(&model).unwrap();
+    let reparsed: T = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reparsed).unwrap(), serialized);
+    model
+}
+
+macro_rules! fixtures {
+    ($($name:ident: $type:ty,)*) => {
+        $(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn deserializes() {
+                    round_trip::<$type>(stringify!($name));
+                }
+
+                #[test]
+                fn unknown_field() {
+                    let value = with_unknown_field(fixture(stringify!($name)));
+                    let result = serde_json::from_value::<$type>(value);
+                    if cfg!(feature = "strict") {
+                        let err = result.expect_err("strict mode accepted an unknown field");
+                        assert!(err.to_string().contains(UNKNOWN_FIELD), "{}", err);
+                    } else {
+                        result.unwrap();
+                    }
+                }
+            }
+        )*
+    };
+}
+
+fixtures! {
+    album: FullAlbum,
+    albums: FullAlbums,
+    saved_albums: Page<SavedAlbum>,
+    new_releases: NewReleases,
+    artist: FullArtist,
+    artists: FullArtists,
+    artist_albums: Page<SimplifiedAlbum>,
+    top_artists: Page<FullArtist>,
+    followed_artists: FollowedArtists,
+    related_artists: RelatedArtists,
+    track: FullTrack,
+    tracks: FullTracks,
+    artist_top_tracks: ArtistTopTracks,
+    saved_tracks: Page<SavedTrack>,
+    audio_features: AudioFeatures,
+    several_audio_features: AudioFeaturesPayload,
+    audio_analysis: AudioAnalysis,
+    playlist: FullPlaylist,
+    current_user_playlists: Page<SimplifiedPlaylist>,
+    playlist_items: Page<PlaylistTrack>,
+    featured_playlists: FeaturedPlaylists,
+    playlist_snapshot: CUDResult,
+    playlist_cover_image: Vec<Image>,
+    current_user: PrivateUser,
+    user: PublicUser,
+    show: FullShow,
+    shows: SeversalSimplifiedShows,
+    saved_shows: Page<Show>,
+    episode: FullEpisode,
+    episodes: FullEpisodes,
+    saved_episodes: Page<SavedEpisode>,
+    audiobook: FullAudiobook,
+    audiobooks: FullAudiobooks,
+    saved_audiobooks: Page<SimplifiedAudiobook>,
+    chapter: FullChapter,
+    chapters: FullChapters,
+    devices: DevicePayload,
+    playback_state: CurrentlyPlaybackContext,
+    currently_playing: CurrentlyPlayingContext,
+    recently_played: CursorBasedPage<PlayHistory>,
+    queue: Queue,
+    recommendations: Recommendations,
+    genre_seeds: GenreSeeds,
+    markets: Markets,
+    category: Category,
+    categories: PageCategory,
+    search: SearchResults,
+    search_audiobooks: SearchResults,
+}
+
+#[test]
+fn unknown_ids_are_none() {
+    let artists: FullArtists = round_trip("artists");
+    assert!(artists.artists[0].is_some());
+    assert!(artists.artists[1].is_none());
+    let tracks: FullTracks = round_trip("tracks");
+    assert_eq!(tracks.tracks.len(), 2);
+    assert!(tracks.tracks[1].is_none());
+}
+
+#[test]
+fn external_urls_of_other_types_are_kept() {
+    let mut value = fixture("artist");
+    value["external_urls"]["wikipedia"] = json!("https://en.wikipedia.org/wiki/Band_of_Horses");
+    let artist: FullArtist = serde_json::from_value(value.clone()).unwrap();
+
+    let urls = &artist.external_urls;
+    assert!(urls.spotify.is_some());
+    assert_eq!(
+        urls.extra.get("wikipedia").map(String::as_str),
+        Some("https://en.wikipedia.org/wiki/Band_of_Horses")
+    );
+    assert_eq!(
+        serde_json::to_value(&artist).unwrap()["external_urls"],
+        value["external_urls"]
+    );
+}
+
+#[cfg(feature = "strict")]
+#[test]
+fn strict_rejects_nested_unknown_fields() {
+    let mut value = fixture("album");
+    value["tracks"]["items"][1]["artists"][0][UNKNOWN_FIELD] = json!(true);
+    let err = serde_json::from_value::<FullAlbum>(value).unwrap_err();
+    assert!(err.to_string().contains(UNKNOWN_FIELD), "{}", err);
+}
+
+#[cfg(feature = "strict")]
+#[test]
+fn strict_keeps_an_empty_extra() {
+    let album: FullAlbum = round_trip("album");
+    assert!(album.extra.is_empty());
+}
+
+#[cfg(not(feature = "strict"))]
+mod extra {
+    use super::*;
+
+    #[test]
+    fn known_fields_are_not_extra() {
+        let album: FullAlbum = round_trip("album");
+        assert!(album.extra.is_empty(), "{:?}", album.extra);
+        assert!(album.tracks.items.iter().all(|t| t.extra.is_empty()));
+        let albums: Page<SimplifiedAlbum> = round_trip("artist_albums");
+        assert!(
+            albums.items[0].extra.is_empty(),
+            "{:?}",
+            albums.items[0].extra
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_kept() {
+        let album: FullAlbum =
+            serde_json::from_value(with_unknown_field(fixture("album"))).unwrap();
+        assert_eq!(album.extra.len(), 1);
+        assert_eq!(album.extra[UNKNOWN_FIELD], json!({ "nested": [1, 2, 3] }));
+        assert_eq!(album.release_date.to_string(), "2006-03-21");
+    }
+
+    #[test]
+    fn unknown_fields_survive_serialization() {
+        let mut value = fixture("playback_state");
+        value["item"]["album"]["artists"][0][UNKNOWN_FIELD] = json!("artist");
+        value["device"][UNKNOWN_FIELD] = json!(42);
+        let playback: CurrentlyPlaybackContext = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(playback.device.extra[UNKNOWN_FIELD], json!(42));
+
+        let serialized = serde_json::to_value(&playback).unwrap();
+        assert_eq!(serialized["device"][UNKNOWN_FIELD], json!(42));
+        assert_eq!(
+            serialized["item"]["album"]["artists"][0][UNKNOWN_FIELD],
+            json!("artist")
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! The current user's top artists and tracks, against a client that pages through the fixtures.
+#![cfg(feature = "api")]
+
+use futures::TryStreamExt;
+use http_types::StatusCode;
+use serde_json::{json, Value};
+
+use spotify_api::api::personalization;
+use spotify_api::model::senum::TimeRange;
+
+mod common;
+use common::{fixture_value, id, respond, Fixtures, Sent, Stub};
+
+const TOP_ITEMS: usize = 5;
+
+/// Top item `n` of `kind`, with its ID made of `n`.
+fn top_item(kind: &str, n: usize) -> Value {
+    let mut item = match kind {
+        "artists" => fixture_value("top_artists")["items"][0].clone(),
+        "tracks" => fixture_value("track"),
+        kind => panic!("unexpected top item type {}", kind),
+    };
+    item["id"] = json!(id(n));
+    item
+}
+
+/// A page of `TOP_ITEMS` top artists or tracks made from the fixtures, paged the way Spotify
+/// pages them.
+fn top_page(sent: &Sent) -> http_client::Response {
+    let kind = sent.last_segment();
+    let param =
+        |name: &str, default: usize| sent.param(name).map_or(default, |v| v.parse().unwrap());
+    let (limit, offset) = (param("limit", 20), param("offset", 0));
+
+    let end = TOP_ITEMS.min(offset + limit);
+    let items: Vec<Value> = (offset + 1..=end).map(|n| top_item(&kind, n)).collect();
+    let next = if end < TOP_ITEMS {
+        let time_range = sent.param("time_range").map(|v| ("time_range", v));
+        let mut next = sent.url.clone();
+        next.query_pairs_mut()
+            .clear()
+            .append_pair("offset", &end.to_string())
+            .append_pair("limit", &limit.to_string())
+            .extend_pairs(time_range);
+        Some(next.to_string())
+    } else {
+        None
+    };
+    let page = json!({
+        "href": sent.url.to_string(),
+        "items": items,
+        "limit": limit,
+        "next": next,
+        "offset": offset,
+        "previous": null,
+        "total": TOP_ITEMS,
+    });
+    respond(StatusCode::Ok, page)
+}
+
+fn top() -> Stub<Fixtures> {
+    Stub(Fixtures::new().route("/v1/me/top/*", top_page))
+}
+
+#[async_std::test]
+async fn every_query_parameter_is_sent() {
+    let client = top();
+
+    let artists = personalization::top_artists_with_options(
+        &client,
+        Some(2),
+        Some(1),
+        Some(TimeRange::ShortTerm),
+    )
+    .await
+    .unwrap();
+    let tracks =
+        personalization::top_tracks_with_options(&client, None, Some(3), Some(TimeRange::LongTerm))
+            .await
+            .unwrap();
+    personalization::top_tracks(&client, None).await.unwrap();
+
+    let artist_ids: Vec<String> = artists.items.iter().map(|a| a.id.to_string()).collect();
+    assert_eq!(artist_ids, vec![id(2), id(3)]);
+    let track_ids: Vec<String> = tracks
+        .items
+        .iter()
+        .map(|t| t.id.as_ref().unwrap().to_string())
+        .collect();
+    assert_eq!(track_ids, vec![id(4), id(5)]);
+
+    assert_eq!(
+        client.requests(),
+        vec![
+            "/v1/me/top/artists?limit=2&offset=1&time_range=short_term",
+            "/v1/me/top/tracks?offset=3&time_range=long_term",
+            "/v1/me/top/tracks",
+        ]
+    );
+}
+
+#[async_std::test]
+async fn streams_follow_every_page() {
+    let client = top();
+
+    let artists: Vec<_> =
+        personalization::top_artists_stream(&client, Some(2), Some(TimeRange::MediumTerm))
+            .try_collect()
+            .await
+            .unwrap();
+    let tracks: Vec<_> = personalization::top_tracks_stream(&client, Some(5), None)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let artist_ids: Vec<String> = artists.iter().map(|a| a.id.to_string()).collect();
+    let track_ids: Vec<String> = tracks
+        .iter()
+        .map(|t| t.id.as_ref().unwrap().to_string())
+        .collect();
+    let expected: Vec<String> = (1..=TOP_ITEMS).map(id).collect();
+    assert_eq!(artist_ids, expected);
+    assert_eq!(track_ids, expected);
+
+    assert_eq!(
+        client.requests(),
+        vec![
+            "/v1/me/top/artists?limit=2&time_range=medium_term",
+            "/v1/me/top/artists?offset=2&limit=2&time_range=medium_term",
+            "/v1/me/top/artists?offset=4&limit=2&time_range=medium_term",
+            // A single page when it holds every item
+            "/v1/me/top/tracks?limit=5",
+        ]
+    );
+}
@@ -0,0 +1,149 @@
+//! Play history paging and queueing, against the in-memory fake API.
+#![cfg(feature = "testing")]
+
+use futures::TryStreamExt;
+use http_types::{StatusCode, Url};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use spotify_api::api::fake::FakeSpotify;
+use spotify_api::api::player::{self, PlayedCursor};
+use spotify_api::model::id::TrackId;
+use spotify_api::model::show::FullEpisode;
+use spotify_api::Error;
+
+mod common;
+use common::*;
+
+fn at_millis(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+/// A fake where track `n` of 1 to 5 was played at `n` seconds past the epoch.
+fn spotify_with_history() -> FakeSpotify {
+    let spotify = spotify();
+    for n in 1..=5 {
+        spotify.add_played(track(n), at_millis(n as u64 * 1000));
+    }
+    spotify
+}
+
+#[async_std::test]
+async fn cursors_are_sent_in_milliseconds() {
+    let spotify = spotify();
+
+    let after = PlayedCursor::After(at_millis(1_716_290_000_123));
+    player::recently_played_with_options(&spotify, Some(5), Some(after))
+        .await
+        .unwrap();
+    let before = PlayedCursor::Before(at_millis(1_716_290_000_000));
+    player::recently_played_with_options(&spotify, None, Some(before))
+        .await
+        .unwrap();
+    // Times before the epoch can't be sent, so they are clamped to it
+    let ancient = PlayedCursor::Before(UNIX_EPOCH - Duration::from_secs(1));
+    player::recently_played_with_options(&spotify, None, Some(ancient))
+        .await
+        .unwrap();
+
+    let path = "GET /v1/me/player/recently-played";
+    assert_eq!(
+        spotify.requests(),
+        vec![
+            format!("{}?limit=5&after=1716290000123", path),
+            format!("{}?before=1716290000000", path),
+            format!("{}?before=0", path),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn stream_follows_the_before_cursor() {
+    let spotify = spotify_with_history();
+
+    let history: Vec<_> = player::recently_played_stream(&spotify, Some(2), None)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let played: Vec<TrackId> = history.into_iter().map(|h| h.track.id.unwrap()).collect();
+    let expected: Vec<TrackId> = (1..=5).rev().map(track_id).collect();
+    assert_eq!(played, expected);
+
+    let path = "GET /v1/me/player/recently-played";
+    assert_eq!(
+        spotify.requests(),
+        vec![
+            format!("{}?limit=2", path),
+            format!("{}?limit=2&before=4000", path),
+            format!("{}?limit=2&before=2000", path),
+            // The last page is empty, which ends the stream
+            format!("{}?limit=2&before=1000", path),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn stream_starts_before_the_given_time() {
+    let spotify = spotify_with_history();
+
+    let history: Vec<_> = player::recently_played_stream(&spotify, None, Some(at_millis(3000)))
+        .try_collect()
+        .await
+        .unwrap();
+
+    let played: Vec<TrackId> = history.into_iter().map(|h| h.track.id.unwrap()).collect();
+    assert_eq!(played, vec![track_id(2), track_id(1)]);
+}
+
+#[async_std::test]
+async fn queueing_carries_on_past_failures() {
+    let spotify = spotify();
+    let episode: FullEpisode = fixture("episode", &id(1));
+    spotify.add_episode(episode);
+    let uris = [
+        track_uri(99),
+        track_uri(1),
+        track_uri(98),
+        format!("spotify:episode:{}", id(1)),
+    ];
+
+    let failed = player::add_many_to_queue(&spotify, &uris, Some("device")).await;
+
+    // The fake doesn't know tracks 98 and 99
+    let failed: Vec<(usize, StatusCode)> = failed
+        .into_iter()
+        .map(|(i, e)| match e {
+            Error::Api { status, .. } => (i, status),
+            other => panic!("expected an API error, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        failed,
+        vec![(0, StatusCode::BadRequest), (2, StatusCode::BadRequest)]
+    );
+    let queue = player::queue(&spotify).await.unwrap();
+    assert_eq!(queue.queue.len(), 2);
+
+    // Every item was sent, in order, to the requested device
+    let expected: Vec<String> = uris
+        .iter()
+        .map(|uri| {
+            let mut url = Url::parse("https://api.spotify.com/v1/me/player/queue").unwrap();
+            url.query_pairs_mut()
+                .append_pair("uri", uri)
+                .append_pair("device_id", "device");
+            format!("POST {}?{}", url.path(), url.query().unwrap())
+        })
+        .collect();
+    assert_eq!(spotify.requests()[..4], expected[..]);
+}
+
+#[async_std::test]
+async fn queueing_nothing_sends_nothing() {
+    let spotify = spotify();
+
+    let failed = player::add_many_to_queue::<_, &str>(&spotify, &[], None).await;
+
+    assert!(failed.is_empty());
+    assert!(spotify.requests().is_empty());
+}
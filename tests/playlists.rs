@@ -0,0 +1,171 @@
+//! Reading and editing playlists through the playlist endpoints, against the in-memory fake API.
+#![cfg(feature = "testing")]
+
+use futures::TryStreamExt;
+use serde_json::Value;
+
+use spotify_api::api::fake::FakeSpotify;
+use spotify_api::api::playlists;
+use spotify_api::model::id::PlaylistId;
+
+mod common;
+use common::*;
+
+async fn new_playlist(spotify: &FakeSpotify) -> PlaylistId {
+    playlists::create_playlist(spotify, &me(), "Road trip")
+        .await
+        .unwrap()
+        .id
+}
+
+/// The URIs of every item in the playlist, in order.
+async fn item_uris(spotify: &FakeSpotify, id: &PlaylistId) -> Vec<String> {
+    let items: Vec<_> = playlists::playlist_items(spotify, id)
+        .await
+        .unwrap()
+        .into_stream(spotify)
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    items.into_iter().map(|i| i.track.unwrap().uri).collect()
+}
+
+async fn current_snapshot(spotify: &FakeSpotify, id: &PlaylistId) -> String {
+    playlists::playlist(spotify, id).await.unwrap().snapshot_id
+}
+
+/// The method of every request the fake received after the first `skip`.
+fn methods(spotify: &FakeSpotify, skip: usize) -> Vec<String> {
+    spotify
+        .requests()
+        .iter()
+        .skip(skip)
+        .map(|r| r.split(' ').next().unwrap().to_owned())
+        .collect()
+}
+
+#[async_std::test]
+async fn every_query_parameter_is_sent() {
+    let spotify = spotify();
+    let id = new_playlist(&spotify).await;
+
+    let _: Value = playlists::playlist_items_with_options(
+        &spotify,
+        &id,
+        Some("items(track(id))"),
+        Some(10),
+        Some(20),
+        Some("SE"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        spotify.requests()[1],
+        format!(
+            "GET /v1/playlists/{}/tracks\
+             ?fields=items%28track%28id%29%29&limit=10&offset=20&market=SE",
+            id
+        )
+    );
+}
+
+#[async_std::test]
+async fn items_are_added_100_at_a_time_in_order() {
+    let spotify = spotify_with_tracks(260);
+    let id = new_playlist(&spotify).await;
+    let first: Vec<String> = (1..=5).map(track_uri).collect();
+    playlists::add_items(&spotify, &id, &first).await.unwrap();
+    let sent = spotify.requests().len();
+
+    let uris: Vec<String> = (11..=260).map(track_uri).collect();
+    let result = playlists::add_items_with_options(&spotify, &id, &uris, Some(5))
+        .await
+        .unwrap();
+
+    // The fake refuses more than 100 items at once, and each chunk is inserted after the ones
+    // before it
+    assert_eq!(methods(&spotify, sent), vec!["POST", "POST", "POST"]);
+    assert_eq!(result.snapshot_id, current_snapshot(&spotify, &id).await);
+    assert_eq!(item_uris(&spotify, &id).await, [first, uris].concat());
+
+    let id = new_playlist(&spotify).await;
+    let sent = spotify.requests().len();
+    let uris: Vec<String> = (1..=101).map(track_uri).collect();
+    playlists::add_items(&spotify, &id, &uris).await.unwrap();
+    assert_eq!(methods(&spotify, sent), vec!["POST", "POST"]);
+    assert_eq!(item_uris(&spotify, &id).await, uris);
+}
+
+#[async_std::test]
+async fn replacing_many_items_puts_the_first_100_and_adds_the_rest() {
+    let spotify = spotify_with_tracks(201);
+    let id = new_playlist(&spotify).await;
+    playlists::add_items(&spotify, &id, &[track_uri(1)])
+        .await
+        .unwrap();
+    let sent = spotify.requests().len();
+
+    let uris: Vec<String> = (1..=201).rev().map(track_uri).collect();
+    let result = playlists::replace_items(&spotify, &id, &uris)
+        .await
+        .unwrap();
+
+    assert_eq!(methods(&spotify, sent), vec!["PUT", "POST", "POST"]);
+    assert_eq!(result.snapshot_id, current_snapshot(&spotify, &id).await);
+    assert_eq!(item_uris(&spotify, &id).await, uris);
+
+    // Replacing with nothing clears the playlist
+    let sent = spotify.requests().len();
+    playlists::replace_items::<_, String>(&spotify, &id, &[])
+        .await
+        .unwrap();
+    assert_eq!(methods(&spotify, sent), vec!["PUT"]);
+    assert!(item_uris(&spotify, &id).await.is_empty());
+}
+
+#[async_std::test]
+async fn items_are_removed_100_at_a_time_against_the_latest_snapshot() {
+    let spotify = spotify_with_tracks(160);
+    let id = new_playlist(&spotify).await;
+    let uris: Vec<String> = (1..=160).map(track_uri).collect();
+    playlists::add_items(&spotify, &id, &uris).await.unwrap();
+    let original = current_snapshot(&spotify, &id).await;
+    let sent = spotify.requests().len();
+
+    // The fake refuses positions from an older snapshot, so the second chunk has to be sent
+    // against the snapshot the first one made
+    let result = playlists::remove_items(&spotify, &id, &uris[..150], Some(&original))
+        .await
+        .unwrap();
+
+    assert_eq!(methods(&spotify, sent), vec!["DELETE", "DELETE"]);
+    assert_eq!(result.snapshot_id, current_snapshot(&spotify, &id).await);
+    assert_eq!(item_uris(&spotify, &id).await, &uris[150..]);
+}
+
+#[async_std::test]
+async fn adding_or_removing_nothing_only_reads_the_snapshot() {
+    let spotify = spotify();
+    let id = new_playlist(&spotify).await;
+    playlists::add_items(&spotify, &id, &[track_uri(1)])
+        .await
+        .unwrap();
+    let snapshot = current_snapshot(&spotify, &id).await;
+    let sent = spotify.requests().len();
+
+    let added = playlists::add_items_with_options::<_, String>(&spotify, &id, &[], Some(0))
+        .await
+        .unwrap();
+    let removed = playlists::remove_items::<_, String>(&spotify, &id, &[], Some(&snapshot))
+        .await
+        .unwrap();
+
+    // The fake refuses empty changes like Spotify does
+    assert_eq!(added.snapshot_id, snapshot);
+    assert_eq!(removed.snapshot_id, snapshot);
+    let read = format!("GET /v1/playlists/{}?fields=snapshot_id", id);
+    assert_eq!(spotify.requests()[sent..], [read.clone(), read]);
+    assert_eq!(item_uris(&spotify, &id).await, vec![track_uri(1)]);
+}
@@ -0,0 +1,141 @@
+//! Raw requests through `BasicSpotifyClient`, against an HTTP client that records what it sends.
+#![cfg(feature = "api")]
+
+use futures::future::BoxFuture;
+use http_client::{Error, HttpClient, Request, Response};
+use http_types::headers::{AUTHORIZATION, CONTENT_TYPE};
+use http_types::{Method, StatusCode};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+use spotify_api::api::client::BasicSpotifyClient;
+use spotify_api::api::raw;
+use spotify_api::oauth::FileCache;
+
+#[derive(Debug)]
+struct Sent {
+    method: String,
+    url: String,
+    authorization: Option<String>,
+    content_type: Option<String>,
+    body: String,
+}
+
+/// Answers every request with `status` and `body`, and keeps what was sent.
+#[derive(Debug)]
+struct Recorder {
+    status: StatusCode,
+    body: &'static str,
+    sent: Arc<Mutex<Vec<Sent>>>,
+}
+
+impl HttpClient for Recorder {
+    fn send(&self, mut req: Request) -> BoxFuture<'static, Result<Response, Error>> {
+        let header = |name| req.header(name).map(|v| v.as_str().to_owned());
+        let sent = Sent {
+            method: req.method().to_string(),
+            url: req.url().to_string(),
+            authorization: header(AUTHORIZATION),
+            content_type: header(CONTENT_TYPE),
+            body: String::new(),
+        };
+        let mut resp = Response::new(self.status);
+        resp.set_body(self.body);
+        let sent_requests = Arc::clone(&self.sent);
+        Box::pin(async move {
+            let body = req.body_string().await?;
+            sent_requests.lock().unwrap().push(Sent { body, ..sent });
+            Ok(resp)
+        })
+    }
+}
+
+type Client = BasicSpotifyClient<Recorder, FileCache>;
+
+/// A client answering with `status` and `body`, and the requests it sends.
+fn client(status: StatusCode, body: &'static str) -> (Client, Arc<Mutex<Vec<Sent>>>) {
+    let path = std::env::temp_dir().join(format!(
+        "spotify-api-raw-{}-{:?}.json",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    let token = json!({
+        "access_token": "token",
+        "token_type": "Bearer",
+        "expires_in": 3600,
+        "expires_at": 0,
+        "refresh_token": "refresh",
+        "scope": "",
+    });
+    std::fs::write(&path, token.to_string()).unwrap();
+    let cache = FileCache::new(path.clone()).unwrap();
+    std::fs::remove_file(path).unwrap();
+    let sent = Arc::default();
+    let recorder = Recorder {
+        status,
+        body,
+        sent: Arc::clone(&sent),
+    };
+    (BasicSpotifyClient::new(recorder, cache), sent)
+}
+
+#[async_std::test]
+async fn raw_requests_are_authorized_and_typed() {
+    let (client, sent) = client(StatusCode::Ok, r#"{ "devices": [] }"#);
+
+    let body = json!({ "device_ids": ["74ASZWbe4lXaubB36ztrGX"] });
+    let devices: Value = raw::request(
+        &client,
+        Method::Put,
+        "/me/player",
+        &[("market", "SE"), ("additional_types", "track,episode")],
+        Some(&body),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(devices, json!({ "devices": [] }));
+    let sent = &sent.lock().unwrap()[0];
+    assert_eq!(sent.method, "PUT");
+    assert_eq!(
+        sent.url,
+        "https://api.spotify.com/v1/me/player?market=SE&additional_types=track%2Cepisode"
+    );
+    assert_eq!(sent.authorization.as_deref(), Some("Bearer token"));
+    assert!(sent
+        .content_type
+        .as_deref()
+        .unwrap()
+        .starts_with("application/json"));
+    assert_eq!(serde_json::from_str::<Value>(&sent.body).unwrap(), body);
+}
+
+#[async_std::test]
+async fn empty_responses_and_bodiless_requests() {
+    let (client, sent) = client(StatusCode::NoContent, "");
+
+    raw::request::<_, ()>(&client, Method::Post, "me/player/next", &[], None)
+        .await
+        .unwrap();
+
+    let sent = &sent.lock().unwrap()[0];
+    assert_eq!(sent.url, "https://api.spotify.com/v1/me/player/next");
+    assert_eq!(sent.content_type, None);
+    assert!(sent.body.is_empty());
+}
+
+#[async_std::test]
+async fn errors_are_parsed_like_wrapped_endpoints() {
+    let body = r#"{ "error": { "status": 404, "message": "Player command failed: No active device found" } }"#;
+    let (client, _) = client(StatusCode::NotFound, body);
+
+    let result = raw::request::<_, Value>(&client, Method::Post, "me/player/next", &[], None).await;
+
+    match result {
+        Err(spotify_api::Error::Api { status, message }) => {
+            assert_eq!(status, StatusCode::NotFound);
+            assert_eq!(message, "Player command failed: No active device found");
+        }
+        other => panic!("expected an API error, got {:?}", other),
+    }
+}
@@ -0,0 +1,184 @@
+//! Recommendation seed checks, against a client that answers with the fixtures.
+#![cfg(feature = "api")]
+
+use spotify_api::api::browse::{self, check_seeds, RecommendationOptions};
+use spotify_api::model::id::{ArtistId, TrackId};
+use spotify_api::model::senum::TrackAttribute;
+use spotify_api::Error as SpotifyError;
+
+mod common;
+use common::{Fixtures, Stub};
+
+const GENRES: &[&str] = &["acoustic", "afrobeat", "alt-rock"];
+
+fn artists(n: usize) -> Vec<ArtistId> {
+    (0..n)
+        .map(|i| format!("{:0>22}", i).parse().unwrap())
+        .collect()
+}
+
+fn tracks(n: usize) -> Vec<TrackId> {
+    (0..n)
+        .map(|i| format!("{:0>22}", i).parse().unwrap())
+        .collect()
+}
+
+/// Options with just these seeds.
+fn seeds<'a>(
+    seed_artists: &'a [ArtistId],
+    seed_genres: &'a [&'a str],
+    seed_tracks: &'a [TrackId],
+) -> RecommendationOptions<'a> {
+    RecommendationOptions {
+        seed_artists,
+        seed_genres,
+        seed_tracks,
+        ..RecommendationOptions::default()
+    }
+}
+
+fn seed_count(result: spotify_api::Result<()>) -> usize {
+    match result {
+        Err(SpotifyError::SeedCount(count)) => count,
+        other => panic!("expected a seed count error, got {:?}", other),
+    }
+}
+
+#[test]
+fn one_to_five_seeds_are_accepted() {
+    let genres = ["acoustic"];
+    assert!(check_seeds(GENRES, &seeds(&artists(1), &[], &[])).is_ok());
+    assert!(check_seeds(GENRES, &seeds(&[], &genres, &[])).is_ok());
+    assert!(check_seeds(GENRES, &seeds(&[], &[], &tracks(5))).is_ok());
+    assert!(check_seeds(GENRES, &seeds(&artists(2), &genres, &tracks(2))).is_ok());
+}
+
+#[test]
+fn seeds_are_counted_across_kinds() {
+    let none = RecommendationOptions::default();
+    assert_eq!(seed_count(check_seeds(GENRES, &none)), 0);
+    assert_eq!(
+        seed_count(check_seeds(GENRES, &seeds(&[], &[], &tracks(6)))),
+        6
+    );
+    assert_eq!(
+        seed_count(check_seeds(
+            GENRES,
+            &seeds(&artists(3), &["acoustic", "afrobeat"], &tracks(1))
+        )),
+        6
+    );
+}
+
+#[test]
+fn genres_must_be_available() {
+    match check_seeds(GENRES, &seeds(&[], &["acoustic", "vaporwave"], &[])) {
+        Err(SpotifyError::UnknownGenreSeed(genre)) => assert_eq!(genre, "vaporwave"),
+        other => panic!("expected an unknown genre error, got {:?}", other),
+    }
+    // Genre seeds are matched exactly
+    assert!(check_seeds(GENRES, &seeds(&[], &["Acoustic"], &[])).is_err());
+    // Too many seeds is reported before unknown genres
+    assert_eq!(
+        seed_count(check_seeds(GENRES, &seeds(&[], &["vaporwave"], &tracks(5)))),
+        6
+    );
+}
+
+/// Serves the genre seed and recommendation fixtures.
+fn browse() -> Stub<Fixtures> {
+    Stub(
+        Fixtures::new()
+            .fixture("/v1/recommendations/available-genre-seeds", "genre_seeds")
+            .fixture("/v1/recommendations", "recommendations"),
+    )
+}
+
+#[async_std::test]
+async fn genres_are_only_fetched_when_checking_genre_seeds() {
+    let client = browse();
+    let seed_artists = artists(1);
+
+    let every_attribute = RecommendationOptions {
+        max_attributes: &[TrackAttribute::Energy(0.5), TrackAttribute::Tempo(120.0)],
+        target_attributes: &[TrackAttribute::Popularity(60)],
+        ..seeds(&seed_artists, &[], &[])
+    };
+    browse::checked_recommendations_with_options(&client, &every_attribute)
+        .await
+        .unwrap();
+    let genre = RecommendationOptions {
+        limit: Some(10),
+        ..seeds(&[], &["alt-rock"], &[])
+    };
+    browse::checked_recommendations_with_options(&client, &genre)
+        .await
+        .unwrap();
+
+    let base = "/v1/recommendations";
+    assert_eq!(
+        client.requests(),
+        vec![
+            // Every attribute is sent
+            format!(
+                "{}?max_energy=0.5&max_tempo=120&target_popularity=60&seed_artists={}",
+                base, seed_artists[0]
+            ),
+            format!("{}/available-genre-seeds", base),
+            format!("{}?limit=10&seed_genres=alt-rock", base),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn invalid_seeds_are_rejected_without_asking_for_recommendations() {
+    let client = browse();
+
+    let result =
+        browse::checked_recommendations_with_options(&client, &seeds(&[], &["vaporwave"], &[]))
+            .await;
+
+    assert!(matches!(result, Err(SpotifyError::UnknownGenreSeed(_))));
+    assert_eq!(
+        client.requests(),
+        vec!["/v1/recommendations/available-genre-seeds"]
+    );
+}
+
+#[async_std::test]
+async fn known_genres_are_not_fetched_again() {
+    let client = browse();
+    let genres = browse::available_genre_seeds(&client).await.unwrap().genres;
+
+    for seed in &["acoustic", "alt-rock"] {
+        let options = seeds(&[], std::slice::from_ref(seed), &[]);
+        browse::checked_recommendations_with_genres(&client, &genres, &options)
+            .await
+            .unwrap();
+    }
+    let options = seeds(&[], &["vaporwave"], &[]);
+    let result = browse::checked_recommendations_with_genres(&client, &genres, &options).await;
+
+    assert!(matches!(result, Err(SpotifyError::UnknownGenreSeed(_))));
+    let base = "/v1/recommendations";
+    assert_eq!(
+        client.requests(),
+        vec![
+            format!("{}/available-genre-seeds", base),
+            format!("{}?seed_genres=acoustic", base),
+            format!("{}?seed_genres=alt-rock", base),
+        ]
+    );
+}
+
+#[test]
+fn attributes_are_named_without_a_prefix() {
+    assert_eq!(
+        TrackAttribute::TimeSignature(4).name_and_value(),
+        ("time_signature", "4".to_owned())
+    );
+    assert_eq!(
+        TrackAttribute::Loudness(-5.5).name_and_value(),
+        ("loudness", "-5.5".to_owned())
+    );
+}
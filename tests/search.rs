@@ -0,0 +1,92 @@
+//! Building the `q` parameter of a search, and searching against a client that answers with
+//! the fixtures.
+#![cfg(feature = "api")]
+
+use spotify_api::model::senum::SearchType;
+use spotify_api::search::{self, SearchQuery};
+
+mod common;
+use common::{Fixtures, Stub};
+
+#[test]
+fn single_words_are_left_alone() {
+    let q = SearchQuery::new()
+        .term("queen")
+        .artist("Queen")
+        .album("Innuendo");
+    assert_eq!(q.to_string(), "queen artist:Queen album:Innuendo");
+}
+
+#[test]
+fn values_with_whitespace_are_quoted() {
+    let q = SearchQuery::new()
+        .term("bohemian rhapsody")
+        .track("The Show Must Go On")
+        .genre("hip\thop");
+    assert_eq!(
+        q.to_string(),
+        "\"bohemian rhapsody\" track:\"The Show Must Go On\" genre:\"hip\thop\""
+    );
+}
+
+#[test]
+fn quotes_are_stripped() {
+    let q = SearchQuery::new()
+        .term(r#"say "hello""#)
+        .artist(r#""Weird Al""#)
+        .album(r#"ab"cd"#);
+    assert_eq!(q.to_string(), r#""say hello" artist:"Weird Al" album:abcd"#);
+}
+
+#[test]
+fn surrounding_whitespace_is_trimmed() {
+    let q = SearchQuery::new().term("  abba  ").artist(" The Beatles\n");
+    assert_eq!(q.to_string(), r#"abba artist:"The Beatles""#);
+}
+
+#[test]
+fn empty_values_are_dropped() {
+    let q = SearchQuery::new()
+        .term("")
+        .artist("   ")
+        .album(r#""""#)
+        .track("Yesterday");
+    assert_eq!(q.to_string(), "track:Yesterday");
+    assert_eq!(SearchQuery::new().term(" ").to_string(), "");
+}
+
+#[test]
+fn years_codes_and_tags() {
+    let q = SearchQuery::new()
+        .year(1991)
+        .years(1970..=1979)
+        .isrc("GBUM71029604")
+        .upc("602537518357")
+        .tag_new()
+        .tag_hipster();
+    assert_eq!(
+        q.to_string(),
+        "year:1991 year:1970-1979 isrc:GBUM71029604 upc:602537518357 tag:new tag:hipster"
+    );
+}
+
+#[async_std::test]
+async fn audiobooks_can_be_searched_for() {
+    let client = Stub(Fixtures::new().fixture("/v1/search", "search_audiobooks"));
+
+    let results = search::search(
+        &client,
+        &SearchQuery::new().term("dune"),
+        &[SearchType::Audiobook],
+    )
+    .await
+    .unwrap();
+
+    let audiobooks = results.audiobooks.unwrap();
+    assert_eq!(
+        audiobooks.items[0].name,
+        "Dune: Book One in the Dune Chronicles"
+    );
+    assert!(results.tracks.is_none());
+    assert_eq!(client.requests(), vec!["/v1/search?q=dune&type=audiobook"]);
+}
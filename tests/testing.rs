@@ -0,0 +1,174 @@
+//! Recording against a client that pretends to be Spotify, then replaying the cassette offline.
+#![cfg(feature = "testing")]
+
+use http_client::{Request, Response};
+use http_types::{Method, StatusCode, Url};
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use spotify_api::api::testing::{Cassette, CassetteClient, REDACTED};
+use spotify_api::api::{library, ClientExt, SpotifyClient};
+use spotify_api::model::id::TrackId;
+
+mod common;
+use common::{respond, Fixtures, Stub};
+
+const SECRET: &str = "BQDxt3MUNY1kbRhOl3bsGzGgt";
+
+/// Keeps a set of saved tracks, and answers anything else with a body full of secrets.
+fn library() -> Stub<Fixtures> {
+    let saved = Arc::new(Mutex::new(Vec::new()));
+    let contains = Arc::clone(&saved);
+    Stub(
+        Fixtures::new()
+            .route("/v1/me/tracks/contains", move |sent| {
+                let saved = contains.lock().unwrap();
+                let contains: Vec<bool> = sent.ids().iter().map(|id| saved.contains(id)).collect();
+                respond(StatusCode::Ok, json!(contains))
+            })
+            .route("/v1/me/tracks", move |sent| {
+                assert_eq!(sent.method, Method::Put);
+                saved.lock().unwrap().extend(sent.ids());
+                Response::new(StatusCode::Ok)
+            })
+            .otherwise(|_| {
+                respond(
+                    StatusCode::Ok,
+                    json!({ "access_token": SECRET, "expires_in": 3600 }),
+                )
+            }),
+    )
+}
+
+fn track_ids() -> Vec<TrackId> {
+    vec![
+        "11dFghVXANMlKmJXsNCbNl".parse().unwrap(),
+        "7ouMYWpwJ422jRcDASZB7P".parse().unwrap(),
+    ]
+}
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("spotify-api-{}-{}.json", name, std::process::id()))
+}
+
+async fn save_and_check<C: SpotifyClient>(client: &C) -> (Vec<bool>, Vec<bool>) {
+    let ids = track_ids();
+    let before = library::library_contains_tracks(client, &ids)
+        .await
+        .unwrap();
+    library::library_save_tracks(client, &ids[..1])
+        .await
+        .unwrap();
+    let after = library::library_contains_tracks(client, &ids)
+        .await
+        .unwrap();
+    (before, after)
+}
+
+#[async_std::test]
+async fn recorded_calls_replay_offline() {
+    let path = cassette_path("library");
+    let recorder = CassetteClient::record(library(), path.clone());
+    let recorded = save_and_check(&recorder).await;
+    recorder.save().unwrap();
+    assert_eq!(recorded, (vec![false, false], vec![true, false]));
+
+    // The same calls in the same order get the same answers, even though the reads match
+    // each other
+    let player = CassetteClient::replay(path.clone()).unwrap();
+    assert_eq!(save_and_check(&player).await, recorded);
+
+    // Reads can be repeated, but anything that wasn't recorded fails
+    let ids = track_ids();
+    let again = library::library_contains_tracks(&player, &ids)
+        .await
+        .unwrap();
+    assert_eq!(again, vec![true, false]);
+    assert!(library::library_save_tracks(&player, &ids).await.is_err());
+    assert!(library::library_contains_tracks(&player, &ids[1..])
+        .await
+        .is_err());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[async_std::test]
+async fn secrets_never_reach_the_cassette() {
+    let path = cassette_path("secrets");
+    let recorder = CassetteClient::record(library(), path.clone());
+
+    let mut url = Url::parse("https://api.spotify.com/v1/unwrapped").unwrap();
+    url.query_pairs_mut()
+        .append_pair("access_token", SECRET)
+        .append_pair("market", "US");
+    let body: serde_json::Value = recorder
+        .send_authorized(Request::new(Method::Get, url))
+        .deserialize_response()
+        .await
+        .unwrap();
+    recorder.save().unwrap();
+
+    // Callers still see the real response
+    assert_eq!(body["access_token"], SECRET);
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(!text.contains(SECRET));
+    let cassette = Cassette::load(&path).unwrap();
+    let interaction = &cassette.interactions[0];
+    assert!(interaction.request.url.contains(REDACTED));
+    assert!(interaction.response.body.contains(REDACTED));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[async_std::test]
+async fn recording_leaves_requests_unchanged() {
+    let library = library();
+    let sent = library.0.clone();
+    let recorder = CassetteClient::record(library, cassette_path("unused"));
+    let mut req = Request::new(
+        Method::Put,
+        Url::parse("https://api.spotify.com/v1/me").unwrap(),
+    );
+    req.set_body(json!({ "name": "Smedjan" }));
+
+    recorder
+        .send_authorized(Request::new(
+            Method::Get,
+            Url::parse("https://api.spotify.com/v1/me").unwrap(),
+        ))
+        .expect_success()
+        .await
+        .unwrap();
+    recorder
+        .send_authorized(req)
+        .expect_success()
+        .await
+        .unwrap();
+
+    let sent = sent.sent();
+    // Requests without a body don't gain a `Content-Type` on the way
+    assert_eq!(sent[0].header("Content-Type"), None);
+    assert_eq!(sent[0].body, "");
+    assert_eq!(sent[1].header("Content-Type"), Some("application/json"));
+    assert_eq!(sent[1].body, r#"{"name":"Smedjan"}"#);
+}
+
+#[async_std::test]
+async fn query_parameter_order_doesnt_matter() {
+    let recorder = CassetteClient::record(library(), cassette_path("unused"));
+    let url = "https://api.spotify.com/v1/unwrapped?market=US&limit=5";
+    recorder
+        .send_authorized(Request::new(Method::Get, Url::parse(url).unwrap()))
+        .expect_success()
+        .await
+        .unwrap();
+
+    let player = CassetteClient::from_cassette(recorder.cassette());
+    let url = "https://api.spotify.com/v1/unwrapped?limit=5&market=US";
+    player
+        .send_authorized(Request::new(Method::Get, Url::parse(url).unwrap()))
+        .expect_success()
+        .await
+        .unwrap();
+}
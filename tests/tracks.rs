@@ -0,0 +1,137 @@
+//! Tracks and their audio features and analysis, against a client that answers with the fixtures.
+#![cfg(feature = "api")]
+
+use http_types::StatusCode;
+use serde_json::{json, Value};
+
+use spotify_api::api::tracks;
+use spotify_api::model::id::TrackId;
+
+mod common;
+use common::{fixture_with_id, id, ids, respond, Fixtures, Sent, Stub};
+
+/// A response to a lookup of several IDs, with the fixture for each of them under `kind`.
+fn several(kind: &'static str, fixture: &'static str) -> impl Fn(&Sent) -> http_client::Response {
+    move |sent| {
+        let items: Vec<Value> = sent
+            .ids()
+            .iter()
+            .map(|id| fixture_with_id(fixture, id))
+            .collect();
+        respond(StatusCode::Ok, json!({ kind: items }))
+    }
+}
+
+/// Serves the track, audio features and audio analysis fixtures, with the ID that was asked for.
+fn catalog() -> Stub<Fixtures> {
+    let one = |fixture: &'static str| {
+        move |sent: &Sent| {
+            respond(
+                StatusCode::Ok,
+                fixture_with_id(fixture, &sent.last_segment()),
+            )
+        }
+    };
+    Stub(
+        Fixtures::new()
+            .route("/v1/tracks", several("tracks", "track"))
+            .route("/v1/tracks/*", one("track"))
+            .route(
+                "/v1/audio-features",
+                several("audio_features", "audio_features"),
+            )
+            .route("/v1/audio-features/*", one("audio_features"))
+            .fixture("/v1/audio-analysis/*", "audio_analysis"),
+    )
+}
+
+#[async_std::test]
+async fn single_lookups_are_sent_by_id() {
+    let client = catalog();
+    let track_id: TrackId = id(1).parse().unwrap();
+
+    let track = tracks::track(&client, &track_id).await.unwrap();
+    let in_market = tracks::track_with_options(&client, &track_id, Some("SE"))
+        .await
+        .unwrap();
+    let features = tracks::audio_features(&client, &track_id).await.unwrap();
+    let analysis = tracks::audio_analysis(&client, &track_id).await.unwrap();
+
+    assert_eq!(track.id, Some(track_id.clone()));
+    assert_eq!(in_market.id, Some(track_id.clone()));
+    assert_eq!(features.id, track_id);
+    assert!(!analysis.sections.is_empty());
+    assert_eq!(
+        client.requests(),
+        vec![
+            format!("/v1/tracks/{}", id(1)),
+            format!("/v1/tracks/{}?market=SE", id(1)),
+            format!("/v1/audio-features/{}", id(1)),
+            format!("/v1/audio-analysis/{}", id(1)),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn several_lookups_keep_the_order_of_ids() {
+    let client = catalog();
+    let wanted: Vec<TrackId> = [3, 1, 2].iter().map(|&n| id(n).parse().unwrap()).collect();
+
+    let found = tracks::tracks_with_options(&client, &wanted, Some("SE"))
+        .await
+        .unwrap()
+        .tracks;
+    let features = tracks::several_audio_features(&client, &wanted)
+        .await
+        .unwrap()
+        .audio_features;
+
+    let found: Vec<TrackId> = found.into_iter().map(|t| t.unwrap().id.unwrap()).collect();
+    let features: Vec<TrackId> = features.into_iter().map(|f| f.unwrap().id).collect();
+    assert_eq!(found, wanted);
+    assert_eq!(features, wanted);
+    let joined = [id(3), id(1), id(2)].join("%2C");
+    assert_eq!(
+        client.requests(),
+        vec![
+            format!("/v1/tracks?ids={}&market=SE", joined),
+            format!("/v1/audio-features?ids={}", joined),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn tracks_are_split_50_at_a_time() {
+    let client = catalog();
+
+    let found = tracks::tracks_with_options(&client, &ids::<TrackId>(101), Some("SE"))
+        .await
+        .unwrap()
+        .tracks;
+
+    assert_eq!(found.len(), 101);
+    assert_eq!(
+        found[100].as_ref().unwrap().id,
+        Some(id(101).parse().unwrap())
+    );
+    assert_eq!(client.id_counts(), vec![50, 50, 1]);
+    // Every chunk is sent for the same market
+    assert!(client
+        .sent()
+        .iter()
+        .all(|sent| sent.param("market").as_deref() == Some("SE")));
+}
+
+#[async_std::test]
+async fn audio_features_are_split_100_at_a_time() {
+    let client = catalog();
+
+    let features = tracks::several_audio_features(&client, &ids::<TrackId>(201))
+        .await
+        .unwrap()
+        .audio_features;
+
+    assert_eq!(features.len(), 201);
+    assert_eq!(features[200].as_ref().unwrap().id, id(201).parse().unwrap());
+    assert_eq!(client.id_counts(), vec![100, 100, 1]);
+}
@@ -0,0 +1,62 @@
+//! User profiles, against a client that answers with the fixtures.
+#![cfg(feature = "api")]
+
+use http_types::StatusCode;
+
+use spotify_api::api::users;
+use spotify_api::model::senum::Product;
+
+mod common;
+use common::{fixture_value, respond, Fixtures, Stub};
+
+/// Serves the user fixtures, leaving out the fields Spotify withholds without
+/// `user-read-private` if `private_fields_withheld`.
+fn users(private_fields_withheld: bool) -> Stub<Fixtures> {
+    let mut me = fixture_value("current_user");
+    if private_fields_withheld {
+        let me = me.as_object_mut().unwrap();
+        for field in &["country", "email", "explicit_content", "product"] {
+            me.remove(*field);
+        }
+    }
+    Stub(
+        Fixtures::new()
+            .route("/v1/me", move |_| respond(StatusCode::Ok, me.clone()))
+            .fixture("/v1/users/smedjan", "user"),
+    )
+}
+
+#[async_std::test]
+async fn current_user_is_read_from_me() {
+    let client = users(false);
+
+    let me = users::current_user(&client).await.unwrap();
+
+    assert_eq!(me.id.to_string(), "smedjan");
+    assert_eq!(me.market(), Some("SE"));
+    assert_eq!(me.product, Some(Product::Premium));
+    assert_eq!(client.requests(), vec!["/v1/me"]);
+}
+
+#[async_std::test]
+async fn private_fields_are_optional() {
+    let client = users(true);
+
+    let me = users::current_user(&client).await.unwrap();
+
+    assert_eq!(me.market(), None);
+    assert_eq!(me.product, None);
+    assert_eq!(me.email, None);
+}
+
+#[async_std::test]
+async fn users_are_read_by_id() {
+    let client = users(false);
+
+    let user = users::user(&client, &"smedjan".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(user.id.to_string(), "smedjan");
+    assert_eq!(client.requests(), vec!["/v1/users/smedjan"]);
+}